@@ -0,0 +1,70 @@
+//! Generates the registry of all solutions in `src/bin/` for the main binary.
+use std::{env, fs, path::Path};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    // solutions are named `DD.rs` (configured year) or `YYYY_DD.rs`.
+    let mut days: Vec<(String, Option<String>, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter_map(|path| {
+                    let stem = path.file_stem()?.to_str()?;
+                    let (year, day) = match stem.split_once('_') {
+                        Some((year, day)) => (Some(year), day),
+                        None => (None, stem),
+                    };
+                    let is_digits =
+                        |s: &str, len| s.len() == len && s.chars().all(|c| c.is_ascii_digit());
+                    let is_day = path.extension()? == "rs"
+                        && is_digits(day, 2)
+                        && year.is_none_or(|year| is_digits(year, 4));
+                    is_day.then(|| {
+                        (
+                            stem.to_string(),
+                            year.map(ToString::to_string),
+                            path.display().to_string(),
+                        )
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    days.sort();
+
+    // NOTE: the registry is left empty for tests, so the tests of each day are not run twice,
+    // and for DHAT builds, as each day defines its own global allocator.
+    let cfg = r#"#[cfg(not(any(test, feature = "dhat-heap")))]"#;
+    let mut out = String::new();
+
+    for (name, _, path) in &days {
+        out.push_str(&format!(
+            "{cfg}\n#[allow(dead_code)]\n#[path = {path:?}]\nmod day_{name};\n\n"
+        ));
+    }
+
+    out.push_str(&format!(
+        "{cfg}\npub const ALL: &[advent_of_code::template::registry::Solution] = &[{}];\n",
+        days.iter()
+            .map(|(name, year, _)| match year {
+                Some(year) => format!(
+                    "advent_of_code::template::registry::Solution {{ \
+                    year: Some(advent_of_code::template::Year::__new_unchecked({year})), \
+                    ..day_{name}::SOLUTION }}"
+                ),
+                None => format!("day_{name}::SOLUTION"),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+    out.push_str(
+        "#[cfg(any(test, feature = \"dhat-heap\"))]\npub const ALL: &[advent_of_code::template::registry::Solution] = &[];\n",
+    );
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, out).unwrap();
+}
@@ -1,20 +1,28 @@
 use std::collections::HashMap;
 
+use advent_of_code::parse::{self, LineError, LineErrorKind};
+use advent_of_code::template::error::InputError;
+
 advent_of_code::solution!(1);
 
 type LocationId = u32;
 
-pub fn parse(input: &str) -> (Vec<LocationId>, Vec<LocationId>) {
-    use itertools::Itertools;
+pub fn parse(input: &str) -> Result<(Vec<LocationId>, Vec<LocationId>), InputError> {
+    let pairs = parse::lines_with(input, |line| {
+        let ids = parse::integers(line)?;
+        <[LocationId; 2]>::try_from(ids).map_err(|ids| {
+            LineError::new(LineErrorKind::Count {
+                expected: 2,
+                found: ids.len(),
+            })
+        })
+    })?;
 
-    let location_ids = input
-        .split(&['\n', ' '][..])
-        .filter_map(|id| id.parse::<LocationId>().ok());
-    location_ids.tuples().unzip()
+    Ok(pairs.into_iter().map(|[left, right]| (left, right)).unzip())
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (mut left, mut right) = parse(input);
+pub fn part_one(input: &str) -> Result<u32, InputError> {
+    let (mut left, mut right) = parse(input)?;
 
     // sort both lists to subtract in order
     left.sort_unstable();
@@ -25,11 +33,11 @@ pub fn part_one(input: &str) -> Option<u32> {
         .zip(right)
         .fold(0, |acc, (left, right)| acc + left.abs_diff(right));
 
-    Some(diff)
+    Ok(diff)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (left, right) = parse(input);
+pub fn part_two(input: &str) -> Result<u32, InputError> {
+    let (left, right) = parse(input)?;
 
     let right_hist = right.into_iter().fold(HashMap::new(), |mut hist, id| {
         *hist.entry(id).or_default() += 1;
@@ -40,7 +48,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         .into_iter()
         .map(|id| id * right_hist.get(&id).unwrap_or(&0));
 
-    Some(weighted_left.sum())
+    Ok(weighted_left.sum())
 }
 
 #[cfg(test)]
@@ -49,13 +57,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11));
+        let result = part_one(&advent_of_code::template::read_file("examples", puzzle()));
+        assert_eq!(result.unwrap(), 11);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(31));
+        let result = part_two(&advent_of_code::template::read_file("examples", puzzle()));
+        assert_eq!(result.unwrap(), 31);
     }
 }
@@ -1,88 +1,50 @@
-advent_of_code::solution!(4);
-
-type Quizz = Vec<Vec<char>>;
-type Coord = (usize, usize);
+use advent_of_code::geometry::Direction8;
+use advent_of_code::grid::{Grid, Position};
+use advent_of_code::parse;
+use advent_of_code::template::error::InputError;
 
-fn parse(input: &str) -> Quizz {
-    input.lines().map(|line| line.chars().collect()).collect()
-}
+advent_of_code::solution!(4);
 
-const XMAS_STENCILS: [[Coord; 4]; 4] = [
-    // normal
-    [(0, 0), (0, 1), (0, 2), (0, 3)],
-    // vertical
-    [(0, 0), (1, 0), (2, 0), (3, 0)],
-    // diagonal(\)
-    [(0, 0), (1, 1), (2, 2), (3, 3)],
-    // diagonal(/)
-    [(0, 3), (1, 2), (2, 1), (3, 0)],
-];
-const XMAS: [[char; 4]; 2] = [['X', 'M', 'A', 'S'], ['S', 'A', 'M', 'X']];
+const XMAS: [char; 4] = ['X', 'M', 'A', 'S'];
 
-const X_MAS_STENCIL: [[Coord; 3]; 2] = [[(0, 0), (1, 1), (2, 2)], [(2, 0), (1, 1), (0, 2)]];
+/// Diagonals through the center of an X-MAS, each read from one corner to the opposite one.
+const X_MAS_STENCIL: [[(isize, isize); 3]; 2] =
+    [[(-1, -1), (0, 0), (1, 1)], [(1, -1), (0, 0), (-1, 1)]];
 const X_MAS: [[char; 3]; 2] = [['M', 'A', 'S'], ['S', 'A', 'M']];
 
-fn matches(quizz: &Quizz, coord: Coord) -> usize {
-    let (x, y) = coord;
-
-    XMAS_STENCILS
-        .map(|stencil| {
-            let word = stencil.map(|(dx, dy)| {
-                *quizz
-                    .get(x + dx)
-                    .and_then(|l| l.get(y + dy))
-                    .unwrap_or(&' ')
-            });
-            XMAS.iter().any(|xmas| xmas == &word)
+fn matches(quizz: &Grid<char>, coord: Position) -> usize {
+    // words are read in every direction, so reversed words are found too
+    Direction8::iter()
+        .filter(|&direction| {
+            quizz
+                .ray(coord, direction)
+                .map(|p| quizz[p])
+                .take(4)
+                .eq(XMAS)
         })
-        .into_iter()
-        .filter(|b| b == &true)
         .count()
 }
 
-fn x_matches(quizz: &Quizz, coord: Coord) -> bool {
-    let (x, y) = coord;
-
-    X_MAS_STENCIL
-        .map(|stencil| {
-            let word = stencil.map(|(dx, dy)| {
-                *quizz
-                    .get(x + dx)
-                    .and_then(|l| l.get(y + dy))
-                    .unwrap_or(&' ')
-            });
-            X_MAS.iter().any(|xmas| xmas == &word)
-        })
-        .into_iter()
-        .all(|b| b)
+fn x_matches(quizz: &Grid<char>, coord: Position) -> bool {
+    X_MAS_STENCIL.iter().all(|stencil| {
+        let word = stencil.map(|offset| quizz.get_offset(coord, offset).copied());
+        X_MAS.iter().any(|x_mas| x_mas.map(Some) == word)
+    })
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let chars = parse(input);
+pub fn part_one(input: &str) -> Result<usize, InputError> {
+    let chars = parse::char_grid(input)?;
 
-    let mut xmas_count = 0;
-    for x in 0..chars.len() {
-        for y in 0..chars[0].len() {
-            let coord = (x, y);
-            xmas_count += matches(&chars, coord);
-        }
-    }
-    Some(xmas_count)
+    Ok(chars.positions().map(|coord| matches(&chars, coord)).sum())
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let chars = parse(input);
+pub fn part_two(input: &str) -> Result<usize, InputError> {
+    let chars = parse::char_grid(input)?;
 
-    let mut xmas_count = 0;
-    for x in 0..chars.len() {
-        for y in 0..chars[0].len() {
-            let coord = (x, y);
-            if x_matches(&chars, coord) {
-                xmas_count += 1;
-            }
-        }
-    }
-    Some(xmas_count)
+    Ok(chars
+        .positions()
+        .filter(|&coord| x_matches(&chars, coord))
+        .count())
 }
 
 #[cfg(test)]
@@ -91,13 +53,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(18));
+        let result = part_one(&advent_of_code::template::read_file("examples", puzzle()));
+        assert_eq!(result.unwrap(), 18);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(9));
+        let result = part_two(&advent_of_code::template::read_file("examples", puzzle()));
+        assert_eq!(result.unwrap(), 9);
     }
 }
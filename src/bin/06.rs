@@ -1,19 +1,21 @@
-use std::collections::HashMap;
-use std::collections::HashSet;
-use std::iter;
 use std::marker::PhantomData;
 
+use advent_of_code::cycle::CycleDetector;
+use advent_of_code::geometry::{self, Direction};
+use advent_of_code::grid::Grid;
+use advent_of_code::replay::Recorder;
+use advent_of_code::template::error::InputError;
+use advent_of_code::viz::{self, Cell, Frame, Rgb, Style};
 use itertools::Itertools;
 
 advent_of_code::solution!(6);
 
 pub type Coord = usize;
-pub type Move = (i8, i8);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SquareState {
     Empty,
-    Guard,
+    Guard(Direction),
     Obstacle,
 }
 
@@ -24,55 +26,11 @@ impl TryFrom<char> for SquareState {
         Ok(match value {
             '.' => Self::Empty,
             '#' => Self::Obstacle,
-            '^' => Self::Guard,
-            unexpected_char => return Err(unexpected_char),
+            guard => Self::Guard(Direction::from_char(guard).ok_or(guard)?),
         })
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
-
-impl Default for Direction {
-    fn default() -> Self {
-        unsafe { Self::iterator().next().unwrap_unchecked() }
-    }
-}
-
-impl Direction {
-    pub fn position_delta(&self) -> Move {
-        match self {
-            Self::North => (0, -1),
-            Self::East => (1, 0),
-            Self::South => (0, 1),
-            Self::West => (-1, 0),
-        }
-    }
-
-    pub fn turn_right(&self) -> Self {
-        match self {
-            Self::North => Self::East,
-            Self::East => Self::South,
-            Self::South => Self::West,
-            Self::West => Self::North,
-        }
-    }
-    pub fn iterator() -> impl Iterator<Item = Self> {
-        static DIRECTIONS: [Direction; 4] = [
-            Direction::North,
-            Direction::East,
-            Direction::South,
-            Direction::West,
-        ];
-        DIRECTIONS.iter().copied()
-    }
-}
-
 #[derive(Debug)]
 pub enum PositionError {
     OutOfBounds,
@@ -84,27 +42,26 @@ pub enum PositionError {
 pub struct Position(pub Coord, pub Coord);
 
 impl Position {
-    pub fn try_move(&self, m: Move) -> Result<Self, PositionError> {
-        let Self(x, y) = self.clone();
-        let (dx, dy) = m;
-
-        let x = x
-            .checked_add_signed(dx as _)
-            .ok_or(PositionError::OutOfBounds)?;
-        let y = y
-            .checked_add_signed(dy as _)
-            .ok_or(PositionError::OutOfBounds)?;
+    pub fn try_move(&self, direction: Direction) -> Result<Self, PositionError> {
+        let &Self(x, y) = self;
+        let (x, y) = geometry::checked_add((x, y), direction).ok_or(PositionError::OutOfBounds)?;
 
         Ok(Position(x, y))
     }
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Guard {
     direction: Direction,
     position: Position,
 }
 
+impl Default for Guard {
+    fn default() -> Self {
+        Self::new(Position::default(), Direction::North)
+    }
+}
+
 impl Guard {
     pub fn new(position: Position, direction: Direction) -> Self {
         Self {
@@ -123,7 +80,7 @@ impl Guard {
     pub fn with_position(position: Position) -> Self {
         Self {
             position,
-            direction: Default::default(),
+            direction: Direction::North,
         }
     }
 
@@ -132,37 +89,18 @@ impl Guard {
         S: StepStrategy<Error = PositionError, Context = Area>,
     {
         let mut guard = self.clone();
+        let mut path = CycleDetector::new();
+        path.push(self);
 
-        // guard states and final error
-        let result_guard_states = iter::from_fn(move || {
-            Some(
-                area.step_guard(&mut guard, step_strategy)
-                    .map(|_| guard.clone()),
-            )
-        });
-
-        // check for loops
-        if let Some((loop_at, _)) = iter::once(Ok(self.clone()))
-            .chain(result_guard_states.clone())
-            .map_while(Result::ok)
-            .enumerate()
-            // FIXME: pls don't clone me :/
-            .duplicates_by(|(_, guard)| guard.clone())
-            .next()
-        {
-            let guard_states = result_guard_states
-                .take(loop_at)
-                .map_while(Result::ok)
-                .collect();
-
-            (guard_states, PositionError::Loop)
-        } else {
-            let mut result_guard_states = iter::once(Ok(self)).chain(result_guard_states);
-
-            let guard_states = result_guard_states.by_ref().map_while(Result::ok).collect();
-            let err = result_guard_states.next().unwrap().unwrap_err();
-
-            (guard_states, err)
+        loop {
+            if let Err(err) = area.step_guard(&mut guard, step_strategy) {
+                return (path.into_states(), err);
+            }
+
+            // a guard that reaches a previous state (position and direction) walks in a loop
+            if path.push(guard.clone()).is_some() {
+                return (path.into_states(), PositionError::Loop);
+            }
         }
     }
 }
@@ -188,38 +126,50 @@ pub trait StepStrategy {
 
 #[derive(Debug, Clone)]
 pub struct Area {
-    width: Coord,
-    height: Coord,
-
-    // TODO: Is there a better way to store this?
-    //   I think a simple 2d-array would be faster but wastes a ton of space
-    obstacles: HashMap<Coord, HashSet<Coord>>,
+    obstacles: Grid<bool>,
 }
 
 impl Area {
     pub fn new(width: Coord, height: Coord) -> Self {
         Self {
-            width,
-            height,
-            obstacles: Default::default(),
+            obstacles: Grid::new(width, height, false),
         }
     }
 
-    pub fn add_obstacle(&mut self, pos: Position) -> Result<(), PositionError> {
-        if !self.in_bound(&pos) {
-            return Err(PositionError::OutOfBounds);
-        }
+    pub fn width(&self) -> Coord {
+        self.obstacles.width()
+    }
 
+    pub fn height(&self) -> Coord {
+        self.obstacles.height()
+    }
+
+    pub fn add_obstacle(&mut self, pos: Position) -> Result<(), PositionError> {
         let Position(x, y) = pos;
+        let square = self
+            .obstacles
+            .get_mut((x, y))
+            .ok_or(PositionError::OutOfBounds)?;
 
-        let present = !self.obstacles.entry(x).or_default().insert(y);
-        if present {
+        if *square {
             return Err(PositionError::Obstacle);
         }
+        *square = true;
 
         Ok(())
     }
 
+    /// The area for visualizations, obstacles drawn as `#`.
+    pub fn frame(&self) -> Frame {
+        Frame::from_grid(&self.obstacles, |&obstacle| {
+            if obstacle {
+                Cell::new('#', Some(Rgb::GRAY))
+            } else {
+                Cell::new('.', None)
+            }
+        })
+    }
+
     pub fn step_guard<S>(&self, guard: &mut Guard, step_strategy: &S) -> Result<(), PositionError>
     where
         S: StepStrategy<Error = PositionError, Context = Self>,
@@ -230,15 +180,15 @@ impl Area {
 
 impl Bounded for Area {
     fn in_bound(&self, position: &Position) -> bool {
-        let Position(x, y) = position;
-        x < &self.width && y < &self.height
+        let Position(x, y) = *position;
+        self.obstacles.contains((x, y))
     }
 }
 
 impl Obstructed for Area {
     fn is_obstructed(&self, pos: &Position) -> bool {
-        let Position(x, y) = pos;
-        self.obstacles.get(x).is_some_and(|line| line.contains(y))
+        let Position(x, y) = *pos;
+        self.obstacles.get((x, y)).is_some_and(|&obstacle| obstacle)
     }
 }
 
@@ -262,9 +212,9 @@ impl<C: Obstructed + Bounded> StepStrategy for SimpleStepPattern<C> {
 
     fn step(&self, guard: &mut Guard, context: &Self::Context) -> Result<(), Self::Error> {
         // only try every direction once for obstacles
-        for _d in Direction::iterator() {
+        for _d in Direction::iter() {
             // step in current direction
-            let new_pos = guard.position.try_move(guard.direction.position_delta())?;
+            let new_pos = guard.position.try_move(guard.direction)?;
 
             // out of bounds!
             if !context.in_bound(&new_pos) {
@@ -288,74 +238,83 @@ impl<C: Obstructed + Bounded> StepStrategy for SimpleStepPattern<C> {
     }
 }
 
-fn charvise(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|line| line.chars().collect()).collect()
+/// Records the guard walking along `path`, leaving visited positions behind.
+fn record_path(area: &Area, path: &[Guard]) {
+    let mut recorder = Recorder::new(puzzle(), "guard-path");
+    if !recorder.is_recording() {
+        return;
+    }
+
+    let mut frame = area.frame();
+    for guard in path {
+        let Position(x, y) = guard.position;
+        frame = frame.overlay([(x, y)], Style::new(guard.direction.arrow(), Rgb::RED));
+        recorder.frame(|| frame.clone());
+        frame = frame.overlay([(x, y)], Style::VISITED);
+    }
 }
 
-fn parse_area(input: &str) -> (Area, Vec<Guard>) {
-    let lines = charvise(input);
-
-    let width = lines
-        .first()
-        .map(|first_line| first_line.len())
-        .unwrap_or_default();
-    let height = lines.len();
-
-    let mut guard = Vec::with_capacity(1);
-    let mut area = Area::new(width, height);
-
-    lines.into_iter().zip(0..).for_each(|(line, y)| {
-        line.into_iter().zip(0..).for_each(|(square, x)| {
-            match SquareState::try_from(square) {
-                Ok(SquareState::Empty) => {}
-                Ok(SquareState::Obstacle) => area
-                    .add_obstacle(Position(x, y))
-                    .expect("should be unobstructed because we iterate over each square once"),
-                // TODO: support different initial guard directions
-                Ok(SquareState::Guard) => guard.push(Guard::with_position(Position(x, y))),
-                Err(unexpected_char) => panic!(
-                    "Unexpected char '{}'at square ({}, {})",
-                    unexpected_char, x, y
-                ),
-            }
-        })
+fn parse_area(input: &str) -> Result<(Area, Guard), InputError> {
+    let squares = Grid::parse_with(input, |square| {
+        SquareState::try_from(square)
+            .map_err(|_| "expected one of `.`, `#` or a guard (`^`, `>`, `v`, `<`)")
+    })?;
+
+    let mut area = Area::new(squares.width(), squares.height());
+
+    let mut guards = squares.iter().filter_map(|((x, y), &square)| match square {
+        SquareState::Guard(direction) => Some(Guard::new(Position(x, y), direction)),
+        _ => None,
     });
+    let Some(guard) = guards.next() else {
+        let line = input.lines().next().unwrap_or_default();
+        return Err(InputError::new(1, line, "the map has no guard"));
+    };
+    if let Some(Guard {
+        position: Position(x, y),
+        ..
+    }) = guards.next()
+    {
+        let line = input.lines().nth(y).unwrap_or_default();
+        return Err(
+            InputError::new(y + 1, line, "the map has more than one guard").at_column(x + 1),
+        );
+    }
 
-    (area, guard)
+    for (x, y) in squares.find_all(|&square| square == SquareState::Obstacle) {
+        area.add_obstacle(Position(x, y))
+            .expect("should be unobstructed because we iterate over each square once");
+    }
+
+    Ok((area, guard))
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
+pub fn part_one(input: &str) -> Result<usize, InputError> {
     let strat = SimpleStepPattern::default();
-    let (area, mut guards) = parse_area(input);
-    let guard = guards.remove(0);
+    let (area, guard) = parse_area(input)?;
 
     let (path, err) = guard.path(&area, &strat);
-    println!("Stop reason while tracing guard path: {:?}", err);
+    log::debug!("stop reason while tracing guard path: {err:?}");
+    record_path(&area, &path);
 
-    let visited = path.into_iter().map(|g| g.position).unique();
+    let visited: Vec<Position> = path.into_iter().map(|g| g.position).unique().collect();
 
-    // mark all visited positions visually
-    //let mut lines = charvise(input);
-    //let visited = visited
-    //    .inspect(|Position(x, y)| lines[*y][*x] = 'X')
-    //    .count();
-    //lines
-    //    .into_iter()
-    //    .for_each(|line| println!("{}", line.into_iter().collect::<String>()));
+    viz::show(puzzle(), "Visited positions", || {
+        area.frame()
+            .overlay(visited.iter().map(|&Position(x, y)| (x, y)), Style::VISITED)
+    });
 
-    Some(visited.count())
+    Ok(visited.len())
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
+pub fn part_two(input: &str) -> Result<usize, InputError> {
     let strat = SimpleStepPattern::default();
-    let (area, mut guards) = parse_area(input);
-
-    let guard = guards.remove(0);
+    let (area, guard) = parse_area(input)?;
 
     // brute force every possible position and check if it creates a loop
-    let loop_obst = (0..area.height)
+    let loop_obst = (0..area.height())
         .flat_map(|y| {
-            (0..area.width).map({
+            (0..area.width()).map({
                 let area = area.clone();
                 let guard = guard.clone();
                 let strat = &strat;
@@ -386,38 +345,14 @@ pub fn part_two(input: &str) -> Option<usize> {
         })
         .flatten();
 
-    // mark all visited positions visually
-    //let loop_count = loop_obst
-    //    .clone()
-    //    .inspect(|(l, p)| {
-    //        let mut lines = charvise(input);
-
-    //        println!("Found loop at {p:?}:");
-
-    //        let Position(x, y) = *p;
-    //        lines[y][x] = 'O';
-
-    //        l.iter().for_each(|g| {
-    //            let Position(x, y) = g.position;
-    //            lines[y][x] = 'X'
-    //        });
-
-    //        lines
-    //            .into_iter()
-    //            .for_each(|line| println!("{}", line.into_iter().collect::<String>()));
-    //    })
-    //    .count();
-
-    //let mut lines = charvise(input);
-    //let loop_count = loop_obst
-    //    .map(|(_, p)| p)
-    //    .inspect(|Position(x, y)| lines[*y][*x] = 'o')
-    //    .count();
-    //lines
-    //    .into_iter()
-    //    .for_each(|line| println!("{}", line.into_iter().collect::<String>()));
-
-    Some(loop_obst.count())
+    let loop_obst: Vec<Position> = loop_obst.collect();
+
+    viz::show(puzzle(), "Obstacles that trap the guard in a loop", || {
+        area.frame()
+            .overlay(loop_obst.iter().map(|&Position(x, y)| (x, y)), Style::PATH)
+    });
+
+    Ok(loop_obst.len())
 }
 
 #[cfg(test)]
@@ -426,13 +361,24 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(41));
+        let result = part_one(&advent_of_code::template::read_file("examples", puzzle()));
+        assert_eq!(result.unwrap(), 41);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
+        let result = part_two(&advent_of_code::template::read_file("examples", puzzle()));
+        assert_eq!(result.unwrap(), 6);
+    }
+
+    #[test]
+    fn test_guards() {
+        let (_, guard) = parse_area("..\n.<").unwrap();
+        assert_eq!(guard, Guard::new(Position(1, 1), Direction::West));
+
+        let error = part_one("..\n..").unwrap_err();
+        assert_eq!(error.line, 1);
+        let error = part_one("^.\n.v").unwrap_err();
+        assert_eq!((error.line, error.column), (2, Some(2)));
     }
 }
@@ -1,47 +1,13 @@
 use std::str::FromStr;
-use strum::EnumIter;
-use strum::IntoEnumIterator;
 
-advent_of_code::solution!(7);
-
-pub trait BinaryFold<T> {
-    fn apply(&self, x: T, y: T) -> T;
-}
+use advent_of_code::equation::{Arithmetic, Solver};
+use advent_of_code::parse::{self, LineError, LineErrorKind};
+use advent_of_code::template::error::InputError;
 
-#[derive(Debug, Clone, Copy, EnumIter)]
-pub enum SimpleOperations {
-    Addition,
-    Multiplication,
-}
-
-impl BinaryFold<u64> for SimpleOperations {
-    fn apply(&self, x: u64, y: u64) -> u64 {
-        match self {
-            Self::Addition => x + y,
-            Self::Multiplication => x * y,
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy, EnumIter)]
-pub enum AdvancedOperations {
-    Addition,
-    Multiplication,
-    Concatination,
-}
+advent_of_code::solution!(7);
 
-impl BinaryFold<u64> for AdvancedOperations {
-    fn apply(&self, x: u64, y: u64) -> u64 {
-        match self {
-            Self::Addition => x + y,
-            Self::Multiplication => x * y,
-            Self::Concatination => {
-                let digits_y = y.ilog10() + 1;
-                x * 10u64.pow(digits_y) + y
-            }
-        }
-    }
-}
+const SIMPLE_OPERATIONS: [Arithmetic; 2] = [Arithmetic::Add, Arithmetic::Multiply];
+const ADVANCED_OPERATIONS: [Arithmetic; 3] = Arithmetic::ALL;
 
 #[derive(Debug, Clone)]
 pub struct OwnedEquation {
@@ -56,102 +22,58 @@ impl OwnedEquation {
         Self { result, numbers }
     }
 
-    pub fn is_satisfyable<B: BinaryFold<u64> + IntoEnumIterator>(&self) -> bool {
-        let Self { result, numbers } = self;
-        let eq = Equation {
-            result: *result,
-            numbers: &numbers[..],
-        };
+    /// The operators that make the numbers evaluate to the result, if any.
+    pub fn solve(&self, operators: &[Arithmetic]) -> Option<Vec<Arithmetic>> {
+        // unwinding the result from the back rules out most operators right away
+        Solver::new(operators).solve_reverse(self.result, &self.numbers)
+    }
 
-        eq.is_satisfyable::<B>(0)
+    pub fn is_satisfyable(&self, operators: &[Arithmetic]) -> bool {
+        self.solve(operators).is_some()
     }
 }
 
 impl FromStr for OwnedEquation {
-    type Err = &'static str;
+    type Err = LineError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let Some((result, numbers)) = s.split_once(':') else {
-            return Err("No delimiter ':' between result and numbers found!");
-        };
+        let (result, numbers) = parse::split_once(s, ":")?;
 
-        let result = result.parse().expect("Result should be an u64");
-        let words = numbers.split_ascii_whitespace();
-        // FIXME: Error instead of panic
-        let numbers: Vec<_> = words.map(|w| w.parse().unwrap()).collect();
+        let result = parse::field(s, result)?;
+        let numbers = numbers
+            .split_ascii_whitespace()
+            .map(|number| parse::field(s, number))
+            .collect::<Result<Vec<_>, _>>()?;
 
         if numbers.len() < 2 {
-            return Err("Need two numbers for an equation");
+            return Err(LineError::new(LineErrorKind::TooFew {
+                min: 2,
+                found: numbers.len(),
+            }));
         }
 
         Ok(Self::new(result, numbers))
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-struct Equation<'a> {
-    result: u64,
-    numbers: &'a [u64],
-}
+pub fn part_one(input: &str) -> Result<u64, InputError> {
+    let equations: Vec<OwnedEquation> = parse::lines(input)?;
 
-impl<'a> Equation<'a> {
-    pub fn is_satisfyable<B: BinaryFold<u64> + IntoEnumIterator>(
-        &self,
-        interim_result: u64,
-    ) -> bool {
-        let Self { result, numbers } = *self;
-
-        for op in B::iter() {
-            let interim_result = op.apply(interim_result, numbers[0]);
-            // all hopes lost if operation makes it larger than required result
-            if interim_result <= result {
-                // interim_result is final result on end
-                if numbers.len() == 1 {
-                    if interim_result == result {
-                        return true;
-                    }
-
-                    // maybe another operation will save the day
-                    continue;
-                }
-
-                // else try applying operation recursively for the left numbers
-                let numbers = &numbers[1..];
-                let reduced_equation = Equation { result, numbers };
-                if reduced_equation.is_satisfyable::<B>(interim_result) {
-                    return true;
-                }
-
-                // else backtrack
-            }
-        }
-
-        false
-    }
+    Ok(equations
+        .iter()
+        .filter(|eq| eq.is_satisfyable(&SIMPLE_OPERATIONS))
+        .map(|satisfyable_eq| satisfyable_eq.result)
+        .sum())
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let lines = input.lines();
+pub fn part_two(input: &str) -> Result<u64, InputError> {
+    let equations: Vec<OwnedEquation> = parse::lines(input)?;
 
-    Some(
-        lines
-            .map(|l| l.parse().unwrap())
-            .filter(OwnedEquation::is_satisfyable::<SimpleOperations>)
-            .map(|satisfyable_eq| satisfyable_eq.result)
-            .sum(),
-    )
-}
-
-pub fn part_two(input: &str) -> Option<u64> {
-    let lines = input.lines();
-
-    Some(
-        lines
-            .map(|l| l.parse().unwrap())
-            .filter(OwnedEquation::is_satisfyable::<AdvancedOperations>)
-            .map(|satisfyable_eq| satisfyable_eq.result)
-            .sum(),
-    )
+    Ok(equations
+        .iter()
+        .filter(|eq| eq.is_satisfyable(&ADVANCED_OPERATIONS))
+        .map(|satisfyable_eq| satisfyable_eq.result)
+        .sum())
 }
 
 #[cfg(test)]
@@ -160,13 +82,20 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3749));
+        let result = part_one(&advent_of_code::template::read_file("examples", puzzle()));
+        assert_eq!(result.unwrap(), 3749);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11387));
+        let result = part_two(&advent_of_code::template::read_file("examples", puzzle()));
+        assert_eq!(result.unwrap(), 11387);
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        let error = part_one("190: 10 19\n3267: 81 x40 27").unwrap_err();
+        assert_eq!((error.line, error.column), (2, Some(10)));
+        assert!(part_one("190 10 19").is_err());
     }
 }
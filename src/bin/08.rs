@@ -1,9 +1,12 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::iter;
 use std::str::FromStr;
-use std::usize;
 
+use advent_of_code::grid::Grid;
+use advent_of_code::parse;
+use advent_of_code::template::error::InputError;
+use advent_of_code::viz::{self, Frame, Rgb, Style};
+use derive_more::derive::{Display, Error, From};
 use itertools::Itertools;
 use lina::{Point2, Vec2};
 
@@ -48,10 +51,20 @@ pub struct Antenna {
     pub position: Coord,
 }
 
-pub enum AntennError {
-    SameNode,
-    Gradient,
+#[derive(Debug, Error, Display, From)]
+pub enum AntennaError {
+    #[display("invalid antenna map")]
+    Input(InputError),
+    #[display("antenna at ({}, {}) is outside of the {width}x{height} area", position[0], position[1])]
+    #[from(ignore)]
+    OutOfBounds {
+        #[error(not(source))]
+        position: Coord,
+        width: usize,
+        height: usize,
+    },
 }
+
 impl Antenna {
     pub(crate) fn line_gradient(&self, other: &Antenna) -> Vector {
         other.position.vec_to(self.position)
@@ -70,11 +83,13 @@ impl Antenna {
         }
     }
 
+    /// Antinodes on the line through both antennas, stepping away from each antenna until leaving the `bounds`.
     pub fn antinode<'a>(
         &'a self,
         other: &'a Antenna,
         bounces: usize,
-    ) -> impl Iterator<Item = Antenna> + use<'a> {
+        bounds: &'a impl Bounds,
+    ) -> impl Iterator<Item = Antenna> + 'a {
         let gradient = self.line_gradient(other);
 
         assert!(
@@ -82,53 +97,98 @@ impl Antenna {
             "Calculate antinodes to other antennas only"
         );
 
-        // careful about not going to minus
-        let mut self_antinotes = iter::repeat(self).enumerate();
         // if we want all bounces we also include ourselves for some reason
-        if bounces != usize::MAX {
-            self_antinotes.by_ref().next();
-        }
-        let self_antinotes = self_antinotes
-            .map_while(move |(i, a)| {
-                let a = a.displace(gradient.map(|c| c * (i as isize)));
-
-                // check out of bounds
-                if a.position[0] >= 0 && a.position[1] >= 0 {
-                    Some(a)
-                } else {
-                    None
-                }
-            })
+        let skip = usize::from(bounces != usize::MAX);
+
+        let self_antinotes = (skip..)
+            .map(move |i| self.displace(gradient.map(|c| c * (i as isize))))
+            .take_while(|a| bounds.contains(a.position))
             .take(bounces);
 
-        let mut other_antinotes = iter::repeat(other).enumerate();
-        // if we want all bounces we also include ourselves for some  reason
-        if bounces != usize::MAX {
-            other_antinotes.by_ref().next();
-        }
-        let other_antinotes = other_antinotes
-            .map_while(move |(i, a)| {
-                let a = a.displace(gradient.map(|c| -c * (i as isize)));
-
-                // check out of bounds
-                if a.position[0] >= 0 && a.position[1] >= 0 {
-                    Some(a)
-                } else {
-                    None
-                }
-            })
+        let other_antinotes = (skip..)
+            .map(move |i| other.displace(gradient.map(|c| -c * (i as isize))))
+            .take_while(|a| bounds.contains(a.position))
             .take(bounces);
 
         self_antinotes.chain(other_antinotes)
     }
 }
 
+/* -------------------------------------------------------------------------- */
+
+/// The area antennas and antinodes are in.
+pub trait Bounds {
+    fn width(&self) -> usize;
+    fn height(&self) -> usize;
+
+    fn contains(&self, position: Coord) -> bool {
+        let in_range = |c: isize, size: usize| usize::try_from(c).is_ok_and(|c| c < size);
+        in_range(position[0], self.width()) && in_range(position[1], self.height())
+    }
+}
+
+/// Bounds of the size of the parsed map.
+#[derive(Debug, Clone, Copy)]
+pub struct Dynamic {
+    width: usize,
+    height: usize,
+}
+
+impl Bounds for Dynamic {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+}
+
+/// Bounds known at compile time, which lets the bounds checks fold into constants.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Fixed<const WIDTH: usize, const HEIGHT: usize>;
+
+impl<const WIDTH: usize, const HEIGHT: usize> Bounds for Fixed<WIDTH, HEIGHT> {
+    fn width(&self) -> usize {
+        WIDTH
+    }
+
+    fn height(&self) -> usize {
+        HEIGHT
+    }
+}
+
 #[derive(Debug)]
-pub struct AntennaArray<const WIDTH: usize, const HEIGHT: usize> {
+pub struct AntennaArray<B: Bounds = Dynamic> {
+    bounds: B,
     antennas_per_freq: HashMap<Frequency, Vec<Coord>>,
+    /// The parsed map, kept for visualizations.
+    map: Grid<char>,
 }
 
-impl<const WIDTH: usize, const HEIGHT: usize> AntennaArray<WIDTH, HEIGHT> {
+impl<B: Bounds> AntennaArray<B> {
+    pub fn insert(&mut self, antenna: Antenna) -> Result<(), AntennaError> {
+        let Antenna {
+            position,
+            frequency,
+        } = antenna;
+
+        if !self.bounds.contains(position) {
+            return Err(AntennaError::OutOfBounds {
+                position,
+                width: self.bounds.width(),
+                height: self.bounds.height(),
+            });
+        }
+
+        self.antennas_per_freq
+            .entry(frequency)
+            .or_default()
+            .push(position);
+
+        Ok(())
+    }
+
     pub fn antinodes(&self, bounces: usize) -> HashSet<Coord> {
         self.antennas_per_freq
             .iter()
@@ -145,125 +205,86 @@ impl<const WIDTH: usize, const HEIGHT: usize> AntennaArray<WIDTH, HEIGHT> {
                             position: *c[1],
                         },
                         bounces,
+                        &self.bounds,
                     )
-                    .map_while(|antinode| {
-                        let pos = antinode.position;
-                        let x: usize = pos[0].try_into().unwrap();
-                        let y: usize = pos[1].try_into().unwrap();
-
-                        // check out of bounds
-                        if x < WIDTH && y < HEIGHT {
-                            Some(pos)
-                        } else {
-                            None
-                        }
-                    })
-                    //.filter(|pos| {
-                    //    // don't create antinode at antenna positions
-                    //    let x: usize = pos[0].try_into().unwrap();
-                    //    let y: usize = pos[1].try_into().unwrap();
-                    //    !self.antennas[y][x]
-                    //})
+                    .map(|antinode| antinode.position)
                     .collect::<Vec<_>>()
                 })
             })
             .collect()
     }
-}
-
-impl<const WIDTH: usize, const HEIGHT: usize> FromIterator<Antenna>
-    for AntennaArray<WIDTH, HEIGHT>
-{
-    fn from_iter<T: IntoIterator<Item = Antenna>>(iter: T) -> Self {
-        let iter = iter.into_iter();
 
-        let mut antennas_per_freq: HashMap<Frequency, Vec<Coord>> =
-            HashMap::with_capacity(iter.size_hint().0);
+    /// Parses the antennas of a map into `bounds`.
+    pub fn parse_with(s: &str, bounds: B) -> Result<Self, AntennaError> {
+        Self::from_map(parse::char_grid(s)?, bounds)
+    }
 
-        for antenna in iter {
-            let Antenna {
-                position,
-                frequency,
-            } = antenna;
-            let x: usize = position[0].try_into().unwrap();
-            let y: usize = position[1].try_into().unwrap();
-
-            assert!(x < WIDTH, "Antennas must be in area");
-            assert!(y < HEIGHT, "Antennas must be in area");
-
-            antennas_per_freq
-                .entry(frequency)
-                .or_default()
-                .push(Coord::new(x as isize, y as isize));
+    /// Places the antennas of a parsed map into `bounds`.
+    fn from_map(map: Grid<char>, bounds: B) -> Result<Self, AntennaError> {
+        let antennas: Vec<Antenna> = map
+            .iter()
+            .filter_map(|((x, y), &c)| {
+                Frequency::new(c)
+                    .map(|frequency| Antenna {
+                        frequency,
+                        position: Coord::new(x as isize, y as isize),
+                    })
+                    .ok()
+            })
+            .collect();
+
+        let mut array = Self {
+            bounds,
+            antennas_per_freq: HashMap::new(),
+            map,
+        };
+        for antenna in antennas {
+            array.insert(antenna)?;
         }
 
-        Self { antennas_per_freq }
+        Ok(array)
     }
 }
 
-impl<const WIDTH: usize, const HEIGHT: usize> FromStr for AntennaArray<WIDTH, HEIGHT> {
-    type Err = &'static str;
+impl FromStr for AntennaArray {
+    type Err = AntennaError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let antennas = s.lines().enumerate().flat_map(|(y, line)| {
-            line.chars().enumerate().filter_map({
-                move |(x, c)| {
-                    Frequency::new(c)
-                        .map(|frequency| Antenna {
-                            frequency,
-                            position: Coord::new(x as isize, y as isize),
-                        })
-                        .ok()
-                }
-            })
-        });
+        let map = parse::char_grid(s)?;
+        let bounds = Dynamic {
+            width: map.width(),
+            height: map.height(),
+        };
 
-        Ok(Self::from_iter(antennas))
+        Self::from_map(map, bounds)
     }
 }
 
-fn charvise(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|line| line.chars().collect()).collect()
-}
-
-pub fn part_one(input: &str) -> Option<usize> {
-    __part_one::<50, 50>(input)
-}
-
-fn __part_one<const WIDTH: usize, const HEIGHT: usize>(input: &str) -> Option<usize> {
-    part::<WIDTH, HEIGHT>(input, 1)
-}
-
-fn part<const WIDTH: usize, const HEIGHT: usize>(input: &str, bounces: usize) -> Option<usize> {
-    let arr = AntennaArray::<WIDTH, HEIGHT>::from_str(input).unwrap();
-
+fn part<B: Bounds>(arr: AntennaArray<B>, bounces: usize) -> usize {
     let antinodes = arr.antinodes(bounces);
-    let count = antinodes.len();
 
-    let mut lines = charvise(input);
-    antinodes.into_iter().for_each(|pos| {
-        let x = pos[0] as usize;
-        let y = pos[1] as usize;
+    viz::show(puzzle(), "Antinodes", || {
+        let map = &arr.map;
+        let positions = antinodes
+            .iter()
+            .map(|pos| (pos[0] as usize, pos[1] as usize));
 
-        if lines[y][x] == '.' {
-            lines[y][x] = '#';
-        } else {
-            lines[y][x] = '+';
-        }
+        // antinodes on antennas keep the frequency, but are colored
+        let (on_antennas, free): (Vec<_>, Vec<_>) = positions.partition(|&pos| map[pos] != '.');
+        Frame::from_chars(map)
+            .overlay(free, Style::ANTINODE)
+            .overlay(on_antennas, Style::color(Rgb::RED))
     });
-    lines
-        .into_iter()
-        .for_each(|line| println!("{}", line.into_iter().collect::<String>()));
 
-    Some(count)
+    antinodes.len()
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    __part_two::<50, 50>(input)
+pub fn part_one(input: &str) -> Result<usize, AntennaError> {
+    Ok(part(input.parse()?, 1))
 }
 
-fn __part_two<const WIDTH: usize, const HEIGHT: usize>(input: &str) -> Option<usize> {
-    part::<WIDTH, HEIGHT>(input, usize::MAX)
+pub fn part_two(input: &str) -> Result<usize, AntennaError> {
+    Ok(part(input.parse()?, usize::MAX))
 }
 
 #[cfg(test)]
@@ -272,13 +293,23 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = __part_one::<12, 12>(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(14));
+        let result = part_one(&advent_of_code::template::read_file("examples", puzzle()));
+        assert_eq!(result.unwrap(), 14);
     }
 
     #[test]
     fn test_part_two() {
-        let result = __part_two::<12, 12>(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(34));
+        let result = part_two(&advent_of_code::template::read_file("examples", puzzle()));
+        assert_eq!(result.unwrap(), 34);
+    }
+
+    #[test]
+    fn test_fixed_bounds() {
+        let input = advent_of_code::template::read_file("examples", puzzle());
+        let fixed = AntennaArray::parse_with(&input, Fixed::<12, 12>).unwrap();
+        assert_eq!(part(fixed, usize::MAX), 34);
+
+        let error = AntennaArray::parse_with(&input, Fixed::<4, 4>).unwrap_err();
+        assert!(matches!(error, AntennaError::OutOfBounds { .. }));
     }
 }
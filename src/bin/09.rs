@@ -1,15 +1,14 @@
-#![feature(iter_array_chunks)]
-
 use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::fmt::Display;
 use std::iter;
-use std::num::ParseIntError;
 use std::str::FromStr;
 
+use advent_of_code::parse::{self, LineError};
+use advent_of_code::replay::Recorder;
+use advent_of_code::viz::Frame;
 use derive_more::derive::Display;
 use derive_more::derive::Error;
-use derive_more::derive::From;
 
 advent_of_code::solution!(9);
 
@@ -81,6 +80,9 @@ impl Display for DiskChunk {
     }
 }
 
+/// The characters per row of replay frames, long disks are wrapped.
+const FRAME_WIDTH: usize = 100;
+
 #[derive(Debug, Clone)]
 pub struct DiskMap {
     chunks: VecDeque<DiskChunk>,
@@ -99,19 +101,16 @@ impl DiskMap {
         chunks.any(|chunk| chunk.chunk_type.is_file())
     }
 
-    pub fn defrag(&mut self) {
-        loop {
-            // find free chunk to move data from the back
-            let Some((free_pointer, mut free_chunk)) = self
-                .chunks
-                .iter()
-                .enumerate()
-                .find(|(_, disk_chunk)| disk_chunk.chunk_type.is_free() && disk_chunk.blocks > 0)
-                .map(|(p, c)| (p, *c))
-            else {
-                break;
-            };
-
+    pub fn defrag(&mut self, recorder: &mut Recorder) {
+        recorder.frame(|| self.frame());
+        // find free chunk to move data from the back
+        while let Some((free_pointer, mut free_chunk)) = self
+            .chunks
+            .iter()
+            .enumerate()
+            .find(|(_, disk_chunk)| disk_chunk.chunk_type.is_free() && disk_chunk.blocks > 0)
+            .map(|(p, c)| (p, *c))
+        {
             // get data block from the back
             let Some((file_pointer, mut file_chunk)) = self
                 .chunks
@@ -124,8 +123,8 @@ impl DiskMap {
                 break;
             };
 
-            //println!("Free: {free_chunk} at {free_chunk}");
-            //println!("File: {file_chunk} at {file_pointer}");
+            log::trace!("free: {free_chunk} at {free_pointer}");
+            log::trace!("file: {file_chunk} at {file_pointer}");
 
             // (partially) move file block into free block
             {
@@ -133,7 +132,7 @@ impl DiskMap {
                 let free_blocks = free_chunk.blocks;
                 // max amount we can fit of the file into free block
                 let delta = usize::min(file_blocks, free_blocks);
-                //println!("Move {delta} of {:?}", file_chunk.chunk_type);
+                log::trace!("move {delta} of {:?}", file_chunk.chunk_type);
 
                 file_chunk.blocks -= delta;
                 free_chunk.blocks -= delta;
@@ -152,7 +151,7 @@ impl DiskMap {
                     },
                 );
             }
-            //println!("Self: {self}");
+            recorder.frame(|| self.frame());
         }
 
         // cleanup unneeded chunks
@@ -164,29 +163,27 @@ impl DiskMap {
         );
     }
 
-    pub fn defrag_whole_file(&mut self) {
+    pub fn defrag_whole_file(&mut self, recorder: &mut Recorder) {
         // FIXME:collapse free chunks?
+        recorder.frame(|| self.frame());
 
         let mut checked_files = HashSet::new();
-        loop {
-            // get data block from the back
-            let Some((file_pointer, mut file_chunk)) = self
-                .chunks
-                .iter()
-                .enumerate()
-                .rev()
-                .find(|(_, disk_chunk)| {
-                    disk_chunk
-                        .chunk_type
-                        .file_id()
-                        .is_some_and(|fid| !checked_files.contains(&fid))
-                        && disk_chunk.blocks > 0
-                })
-                .map(|(p, c)| (p, *c))
-            else {
-                break;
-            };
-            //println!("File: {file_chunk} at {file_pointer}");
+        // get data block from the back
+        while let Some((file_pointer, mut file_chunk)) = self
+            .chunks
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, disk_chunk)| {
+                disk_chunk
+                    .chunk_type
+                    .file_id()
+                    .is_some_and(|fid| !checked_files.contains(&fid))
+                    && disk_chunk.blocks > 0
+            })
+            .map(|(p, c)| (p, *c))
+        {
+            log::trace!("file: {file_chunk} at {file_pointer}");
             checked_files.insert(file_chunk.chunk_type.file_id().unwrap());
 
             // find free chunk to would fit our data
@@ -204,7 +201,7 @@ impl DiskMap {
                 // no fitting place found :(
                 continue;
             };
-            //println!("Free: {free_chunk} at {free_chunk}");
+            log::trace!("free: {free_chunk} at {free_pointer}");
 
             // (partially) move file block into free block
             {
@@ -212,7 +209,7 @@ impl DiskMap {
                 let free_blocks = free_chunk.blocks;
                 // max amount we can fit of the file into free block
                 let delta = usize::min(file_blocks, free_blocks);
-                //println!("Move {delta} of {:?}", file_chunk.chunk_type);
+                log::trace!("move {delta} of {:?}", file_chunk.chunk_type);
 
                 file_chunk.blocks -= delta;
                 free_chunk.blocks -= delta;
@@ -239,13 +236,23 @@ impl DiskMap {
                     },
                 );
             }
-            //println!("Self: {self}");
+            recorder.frame(|| self.frame());
         }
 
         // cleanup unneeded chunks
         self.chunks.retain(|disk_chunk| disk_chunk.blocks > 0);
     }
 
+    /// The disk for replays, wrapped into rows of [`FRAME_WIDTH`] characters.
+    fn frame(&self) -> Frame {
+        let disk: Vec<char> = self.to_string().chars().collect();
+        let rows: Vec<String> = disk
+            .chunks(FRAME_WIDTH)
+            .map(|row| row.iter().collect())
+            .collect();
+        Frame::from_text(&rows.join("\n"))
+    }
+
     // returns one-sized chunks
     pub fn checksum(&self) -> usize {
         self.chunks
@@ -268,69 +275,65 @@ impl Display for DiskMap {
     }
 }
 
-#[derive(Debug, Error, Display, From)]
+#[derive(Debug, Error, Display)]
 pub enum ParseDiskMapError {
-    ParseIntError(ParseIntError),
-    OddNumbers(),
+    #[display("the disk map is empty")]
+    Empty,
+    #[display("invalid disk map at column {column}")]
+    InvalidDigit { column: usize, source: LineError },
 }
+
 impl FromStr for DiskMap {
     type Err = ParseDiskMapError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut disk_chunks = s.lines().next().unwrap().chars().array_chunks::<2>();
+        let line = s.lines().next().ok_or(ParseDiskMapError::Empty)?;
 
-        let mut chunks: VecDeque<_> = disk_chunks
-            .by_ref()
-            .enumerate()
-            .flat_map(|(file_id, disk_chunk)| {
-                let file_size = disk_chunk[0].to_digit(10).unwrap() as usize;
-                let free_size = disk_chunk[1].to_digit(10).unwrap() as usize;
+        // sizes alternate between files and free space, the last file may not be followed by free space
+        let sizes = parse::digits(line).map_err(|source| ParseDiskMapError::InvalidDigit {
+            column: source.column.unwrap_or(1),
+            source,
+        })?;
 
-                [
-                    DiskChunk {
-                        blocks: file_size,
-                        chunk_type: BlockType::File { file_id },
-                    },
-                    DiskChunk {
-                        blocks: free_size,
-                        chunk_type: BlockType::Free(),
-                    },
-                ]
+        let chunks = sizes
+            .chunks(2)
+            .enumerate()
+            .flat_map(|(file_id, sizes)| {
+                let file = DiskChunk {
+                    blocks: sizes[0].into(),
+                    chunk_type: BlockType::File { file_id },
+                };
+                let free = sizes.get(1).map(|&blocks| DiskChunk {
+                    blocks: blocks.into(),
+                    chunk_type: BlockType::Free(),
+                });
+
+                iter::once(file).chain(free)
             })
             .collect();
 
-        if let Some(mut remainder) = disk_chunks.into_remainder() {
-            let file_size = remainder.next().unwrap().to_digit(10).unwrap() as usize;
-            chunks.push_back(DiskChunk {
-                blocks: file_size,
-                chunk_type: BlockType::File {
-                    file_id: chunks.len() / 2,
-                },
-            })
-        }
-
         Ok(Self { chunks })
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let mut disk_map: DiskMap = input.parse().unwrap();
-    //println!("Fragmanted Disk: {}", disk_map);
+pub fn part_one(input: &str) -> Result<usize, ParseDiskMapError> {
+    let mut disk_map: DiskMap = input.parse()?;
+    log::debug!("fragmented disk: {disk_map}");
 
-    disk_map.defrag();
-    //println!("Defragmanted Disk: {}", disk_map);
+    disk_map.defrag(&mut Recorder::new(puzzle(), "defrag"));
+    log::debug!("defragmented disk: {disk_map}");
 
-    Some(disk_map.checksum())
+    Ok(disk_map.checksum())
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let mut disk_map: DiskMap = input.parse().unwrap();
-    //println!("Fragmanted Disk: {}", disk_map);
+pub fn part_two(input: &str) -> Result<usize, ParseDiskMapError> {
+    let mut disk_map: DiskMap = input.parse()?;
+    log::debug!("fragmented disk: {disk_map}");
 
-    disk_map.defrag_whole_file();
-    //println!("Defragmanted Disk: {}", disk_map);
+    disk_map.defrag_whole_file(&mut Recorder::new(puzzle(), "defrag-whole-file"));
+    log::debug!("defragmented disk: {disk_map}");
 
-    Some(disk_map.checksum())
+    Ok(disk_map.checksum())
 }
 
 #[cfg(test)]
@@ -339,13 +342,22 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1928));
+        let result = part_one(&advent_of_code::template::read_file("examples", puzzle()));
+        assert_eq!(result.unwrap(), 1928);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2858));
+        let result = part_two(&advent_of_code::template::read_file("examples", puzzle()));
+        assert_eq!(result.unwrap(), 2858);
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        assert_eq!(
+            part_one("23x3").unwrap_err().to_string(),
+            "invalid disk map at column 3"
+        );
+        assert!(matches!(part_two(""), Err(ParseDiskMapError::Empty)));
     }
 }
@@ -0,0 +1,286 @@
+//! Cycle detection for sequences of states, e.g. simulations that eventually repeat.
+//!
+//! A sequence `x0, x1 = f(x0), x2 = f(x1), ...` of a deterministic step function `f` either ends or runs into a
+//! cycle. Once the cycle is known, the state after any number of steps can be looked up without simulating them:
+//!
+//! ```
+//! use advent_of_code::cycle;
+//!
+//! // 0, 1, 2, 3, 4, 2, 3, 4, ...
+//! let cycle = cycle::brent(0, |&x| if x == 4 { 2 } else { x + 1 });
+//! assert_eq!((cycle.start, cycle.length), (2, 3));
+//! assert_eq!(cycle.state_at(1_000_000_000), Some(&4));
+//! ```
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+
+/// A cycle in a sequence of states.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<T> {
+    /// The number of states before the cycle, i.e. the index of the first state of the cycle.
+    pub start: usize,
+    /// The number of states in the cycle.
+    pub length: usize,
+    /// The states of the cycle, starting with the state at index [`Cycle::start`].
+    pub states: Vec<T>,
+}
+
+impl<T> Cycle<T> {
+    /// The index of the first occurrence of the state at index `n` of the sequence.
+    pub fn index_at(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+
+    /// The state at index `n` of the sequence, [`None`] if `n` is before the cycle.
+    pub fn state_at(&self, n: usize) -> Option<&T> {
+        let index = self.index_at(n);
+        index
+            .checked_sub(self.start)
+            .and_then(|index| self.states.get(index))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Finds the cycle of the sequence `x0, f(x0), f(f(x0)), ...` with Floyd's "tortoise and hare" algorithm.
+///
+/// Only a constant number of states is kept in memory, but `f` is called about three times per step.
+/// The sequence must be infinite, otherwise this never returns.
+pub fn floyd<T: Clone + PartialEq>(x0: T, mut f: impl FnMut(&T) -> T) -> Cycle<T> {
+    // find a repetition x_i = x_2i, i is a multiple of the cycle length then
+    let mut tortoise = f(&x0);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        let next = f(&hare);
+        hare = f(&next);
+    }
+
+    // x_start = x_(start + i), so moving both by start steps meets at the start of the cycle
+    let mut start = 0;
+    let mut tortoise = x0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    cycle_from(start, tortoise, f)
+}
+
+/// Finds the cycle of the sequence `x0, f(x0), f(f(x0)), ...` with Brent's algorithm.
+///
+/// Like [`floyd`] only a constant number of states is kept in memory, but `f` is usually called less often.
+/// The sequence must be infinite, otherwise this never returns.
+pub fn brent<T: Clone + PartialEq>(x0: T, mut f: impl FnMut(&T) -> T) -> Cycle<T> {
+    // search the cycle length in windows of increasing powers of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = x0.clone();
+    let mut hare = f(&x0);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = f(&hare);
+        length += 1;
+    }
+
+    // with the hare `length` steps ahead, both meet at the start of the cycle
+    let mut tortoise = x0.clone();
+    let mut hare = x0;
+    for _ in 0..length {
+        hare = f(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    cycle_from(start, tortoise, f)
+}
+
+/// Collects the states of a cycle, starting at `first`.
+fn cycle_from<T: Clone + PartialEq>(
+    start: usize,
+    first: T,
+    mut f: impl FnMut(&T) -> T,
+) -> Cycle<T> {
+    let mut states = vec![first.clone()];
+    let mut state = f(&first);
+    while state != first {
+        let next = f(&state);
+        states.push(state);
+        state = next;
+    }
+
+    Cycle {
+        start,
+        length: states.len(),
+        states,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Finds the first repeated state of an iterator, returns [`None`] if the iterator ends before.
+///
+/// Unlike [`floyd`] and [`brent`], every state is kept in memory, but the sequence is only evaluated once and the
+/// states do not need to be cloned.
+pub fn detect<T: Hash + Eq>(states: impl IntoIterator<Item = T>) -> Option<Cycle<T>> {
+    let mut detector = CycleDetector::new();
+    for state in states {
+        if let Some(start) = detector.push(state) {
+            return Some(detector.into_cycle(start));
+        }
+    }
+    None
+}
+
+/// Records the states of a sequence until one repeats, see [`detect`].
+///
+/// This is useful when the states are produced by a loop rather than an iterator, or when the states before the
+/// repetition are needed, e.g. to trace a path.
+#[derive(Debug, Clone)]
+pub struct CycleDetector<T> {
+    states: Vec<T>,
+    /// The index of the last state with a hash, and for every state the index of the previous state with the same hash.
+    last_by_hash: HashMap<u64, usize>,
+    previous_by_hash: Vec<Option<usize>>,
+    hasher: RandomState,
+}
+
+impl<T: Hash + Eq> CycleDetector<T> {
+    pub fn new() -> Self {
+        Self {
+            states: vec![],
+            last_by_hash: HashMap::new(),
+            previous_by_hash: vec![],
+            hasher: RandomState::new(),
+        }
+    }
+
+    /// Records the next state of the sequence.
+    ///
+    /// If an equal state was recorded before, returns its index (i.e. the start of the cycle) and drops the state.
+    pub fn push(&mut self, state: T) -> Option<usize> {
+        let hash = self.hasher.hash_one(&state);
+        let mut candidate = self.last_by_hash.get(&hash).copied();
+
+        while let Some(index) = candidate {
+            if self.states[index] == state {
+                return Some(index);
+            }
+            candidate = self.previous_by_hash[index];
+        }
+
+        let index = self.states.len();
+        self.previous_by_hash
+            .push(self.last_by_hash.insert(hash, index));
+        self.states.push(state);
+        None
+    }
+
+    /// The number of recorded states.
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    /// The recorded states in order.
+    pub fn states(&self) -> &[T] {
+        &self.states
+    }
+
+    pub fn into_states(self) -> Vec<T> {
+        self.states
+    }
+
+    /// The cycle that starts at index `start`, as returned by [`CycleDetector::push`].
+    pub fn into_cycle(mut self, start: usize) -> Cycle<T> {
+        let states = self.states.split_off(start);
+        Cycle {
+            start,
+            length: states.len(),
+            states,
+        }
+    }
+}
+
+impl<T: Hash + Eq> Default for CycleDetector<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{brent, detect, floyd, Cycle, CycleDetector};
+
+    /// 0, 1, ..., 6, 3, 4, 5, 6, 3, ...
+    fn step(x: &u32) -> u32 {
+        if *x == 6 {
+            3
+        } else {
+            x + 1
+        }
+    }
+
+    fn expected() -> Cycle<u32> {
+        Cycle {
+            start: 3,
+            length: 4,
+            states: vec![3, 4, 5, 6],
+        }
+    }
+
+    #[test]
+    fn finds_cycles() {
+        assert_eq!(floyd(0, step), expected());
+        assert_eq!(brent(0, step), expected());
+        assert_eq!(
+            detect(std::iter::successors(Some(0), |x| Some(step(x)))),
+            Some(expected())
+        );
+
+        // the whole sequence is a cycle
+        assert_eq!(brent(3, step).start, 0);
+        assert_eq!(floyd(3, step).length, 4);
+        assert_eq!(brent(7, |x| *x).length, 1);
+    }
+
+    #[test]
+    fn extrapolates_cycles() {
+        let cycle = expected();
+        assert_eq!(cycle.index_at(2), 2);
+        assert_eq!(cycle.index_at(7), 3);
+        assert_eq!(cycle.index_at(1_000_000_000), 4);
+        assert_eq!(cycle.state_at(1_000_000_000), Some(&4));
+        assert_eq!(cycle.state_at(1), None);
+    }
+
+    #[test]
+    fn records_states() {
+        assert_eq!(detect([1, 2, 3]), None);
+
+        let mut detector = CycleDetector::new();
+        assert_eq!(detector.push("a"), None);
+        assert_eq!(detector.push("b"), None);
+        assert_eq!(detector.push("a"), Some(0));
+        assert_eq!(detector.states(), &["a", "b"]);
+    }
+}
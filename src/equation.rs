@@ -0,0 +1,525 @@
+//! Solves equations with missing operators, e.g. `3267: 81 ? 40 ? 27`, by trying every operator between the numbers.
+//!
+//! Any type implementing [`Operator`] can be inserted, [`Arithmetic`] covers the usual suspects:
+//!
+//! ```
+//! use advent_of_code::equation::{self, Arithmetic, Solver};
+//!
+//! let solver = Solver::new(&[Arithmetic::Add, Arithmetic::Multiply]);
+//! let operators = solver.solve(3267, &[81, 40, 27]).unwrap();
+//! assert_eq!(equation::expression(&[81, 40, 27], &operators), "81 + 40 * 27");
+//! assert_eq!(solver.solutions(3267, &[81, 40, 27]).len(), 2);
+//! ```
+use std::fmt::Display;
+use std::ops::ControlFlow;
+
+/// A binary operator that can be inserted between two numbers.
+pub trait Operator<T> {
+    /// `x op y`, [`None`] if it is undefined, e.g. on overflow.
+    fn apply(&self, x: T, y: T) -> Option<T>;
+
+    /// How tightly the operator binds with [`Evaluation::Precedence`], higher binds tighter.
+    fn precedence(&self) -> u8 {
+        0
+    }
+
+    /// Whether `x op y >= x` for every `x`, so a left-to-right search can give up once it exceeds the target and only
+    /// such operations are left, e.g. `x * y` grows unless `y` is 0.
+    fn grows(&self, _y: T) -> bool {
+        false
+    }
+}
+
+/// An operator that can be undone, which lets [`Solver::solve_reverse`] unwind the target from the last number.
+pub trait Invertible<T>: Operator<T> {
+    /// The `x` with `x op y == result`, [`None`] if there is none or if it [`absorbs`](Invertible::absorbs) `y`.
+    fn invert(&self, result: T, y: T) -> Option<T>;
+
+    /// Whether `x op y == result` for every `x`, e.g. `x * 0 == 0`.
+    fn absorbs(&self, _result: T, _y: T) -> bool {
+        false
+    }
+}
+
+/// The order in which the operators of an equation are applied.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Evaluation {
+    /// Strictly from left to right, ignoring precedence.
+    #[default]
+    LeftToRight,
+    /// Operators with a higher [`Operator::precedence`] first, otherwise from left to right.
+    Precedence,
+}
+
+/// Evaluates `numbers` with `operators` between them, [`None`] if an operator is undefined for its operands.
+///
+/// # Panics
+///
+/// Panics if there is not exactly one operator less than numbers.
+pub fn evaluate<T: Copy, O: Operator<T>>(
+    numbers: &[T],
+    operators: &[O],
+    evaluation: Evaluation,
+) -> Option<T> {
+    assert_eq!(
+        operators.len() + 1,
+        numbers.len(),
+        "Need an operator between every two numbers"
+    );
+
+    match evaluation {
+        Evaluation::LeftToRight => operators
+            .iter()
+            .zip(&numbers[1..])
+            .try_fold(numbers[0], |x, (operator, &y)| operator.apply(x, y)),
+        Evaluation::Precedence => {
+            // operands and the operators between them that still wait for a weaker operator on their right
+            let mut values = vec![numbers[0]];
+            let mut pending: Vec<&O> = vec![];
+
+            for (operator, &y) in operators.iter().zip(&numbers[1..]) {
+                while let Some(&previous) = pending.last() {
+                    if previous.precedence() < operator.precedence() {
+                        break;
+                    }
+                    reduce(&mut values, previous)?;
+                    pending.pop();
+                }
+                pending.push(operator);
+                values.push(y);
+            }
+
+            while let Some(previous) = pending.pop() {
+                reduce(&mut values, previous)?;
+            }
+            values.pop()
+        }
+    }
+}
+
+/// Replaces the last two values with the result of `operator`.
+fn reduce<T, O: Operator<T>>(values: &mut Vec<T>, operator: &O) -> Option<()> {
+    let y = values.pop()?;
+    let x = values.pop()?;
+    values.push(operator.apply(x, y)?);
+    Some(())
+}
+
+/// Formats `numbers` with `operators` between them, e.g. `81 + 40 * 27`.
+pub fn expression<T: Display, O: Display>(numbers: &[T], operators: &[O]) -> String {
+    let mut expression = numbers.first().map(T::to_string).unwrap_or_default();
+    for (operator, number) in operators.iter().zip(numbers.iter().skip(1)) {
+        expression.push_str(&format!(" {operator} {number}"));
+    }
+    expression
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Searches the operators that make numbers evaluate to a target.
+#[derive(Debug, Clone, Copy)]
+pub struct Solver<'a, O> {
+    operators: &'a [O],
+    evaluation: Evaluation,
+}
+
+impl<'a, O: Clone> Solver<'a, O> {
+    /// A solver that tries `operators` in the given order and evaluates from left to right.
+    pub fn new(operators: &'a [O]) -> Self {
+        Self {
+            operators,
+            evaluation: Evaluation::LeftToRight,
+        }
+    }
+
+    #[must_use]
+    pub fn with_evaluation(mut self, evaluation: Evaluation) -> Self {
+        self.evaluation = evaluation;
+        self
+    }
+
+    /// The first operators (in the order of the operator set) that make `numbers` evaluate to `target`.
+    pub fn solve<T>(&self, target: T, numbers: &[T]) -> Option<Vec<O>>
+    where
+        T: Copy + Ord,
+        O: Operator<T>,
+    {
+        let mut solution = None;
+        let _ = self.search(target, numbers, &mut |operators| {
+            solution = Some(operators.to_vec());
+            ControlFlow::Break(())
+        });
+        solution
+    }
+
+    /// Every assignment of operators that makes `numbers` evaluate to `target`.
+    pub fn solutions<T>(&self, target: T, numbers: &[T]) -> Vec<Vec<O>>
+    where
+        T: Copy + Ord,
+        O: Operator<T>,
+    {
+        let mut solutions = vec![];
+        let _ = self.search(target, numbers, &mut |operators| {
+            solutions.push(operators.to_vec());
+            ControlFlow::Continue(())
+        });
+        solutions
+    }
+
+    fn search<T>(
+        &self,
+        target: T,
+        numbers: &[T],
+        found: &mut impl FnMut(&[O]) -> ControlFlow<()>,
+    ) -> ControlFlow<()>
+    where
+        T: Copy + Ord,
+        O: Operator<T>,
+    {
+        let Some((&first, rest)) = numbers.split_first() else {
+            return ControlFlow::Continue(());
+        };
+
+        // pruning is only sound once no operator can shrink the result anymore
+        let growing = rest
+            .iter()
+            .rev()
+            .take_while(|&&y| self.operators.iter().all(|operator| operator.grows(y)))
+            .count();
+
+        let mut operators = Vec::with_capacity(rest.len());
+        match self.evaluation {
+            Evaluation::LeftToRight => {
+                self.forward(target, first, rest, growing, &mut operators, found)
+            }
+            Evaluation::Precedence => self.exhaustive(target, numbers, &mut operators, found),
+        }
+    }
+
+    /// Applies the operators from left to right while choosing them, so partial results can be pruned once only the
+    /// last `growing` numbers are left.
+    fn forward<T>(
+        &self,
+        target: T,
+        interim: T,
+        numbers: &[T],
+        growing: usize,
+        operators: &mut Vec<O>,
+        found: &mut impl FnMut(&[O]) -> ControlFlow<()>,
+    ) -> ControlFlow<()>
+    where
+        T: Copy + Ord,
+        O: Operator<T>,
+    {
+        let Some((&y, rest)) = numbers.split_first() else {
+            return if interim == target {
+                found(operators)
+            } else {
+                ControlFlow::Continue(())
+            };
+        };
+
+        for operator in self.operators {
+            let Some(interim) = operator.apply(interim, y) else {
+                continue;
+            };
+            // all hopes lost if it is larger than the target and the remaining operators only make it larger
+            if interim > target && rest.len() <= growing {
+                continue;
+            }
+
+            operators.push(operator.clone());
+            self.forward(target, interim, rest, growing, operators, found)?;
+            operators.pop();
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    /// Evaluates every assignment of operators as a whole, precedence rules out pruning partial results.
+    fn exhaustive<T>(
+        &self,
+        target: T,
+        numbers: &[T],
+        operators: &mut Vec<O>,
+        found: &mut impl FnMut(&[O]) -> ControlFlow<()>,
+    ) -> ControlFlow<()>
+    where
+        T: Copy + Ord,
+        O: Operator<T>,
+    {
+        if operators.len() + 1 == numbers.len() {
+            return if evaluate(numbers, operators, self.evaluation) == Some(target) {
+                found(operators)
+            } else {
+                ControlFlow::Continue(())
+            };
+        }
+
+        for operator in self.operators {
+            operators.push(operator.clone());
+            self.exhaustive(target, numbers, operators, found)?;
+            operators.pop();
+        }
+
+        ControlFlow::Continue(())
+    }
+}
+
+impl<O: Clone> Solver<'_, O> {
+    /// Like [`Solver::solve`], but unwinds the target from the last number, which rules out most operators early.
+    ///
+    /// Always evaluates from left to right.
+    pub fn solve_reverse<T>(&self, target: T, numbers: &[T]) -> Option<Vec<O>>
+    where
+        T: Copy + Eq,
+        O: Invertible<T>,
+    {
+        let mut solution = None;
+        let mut operators = Vec::with_capacity(numbers.len());
+        let _ = self.backward(target, numbers, &mut operators, &mut |operators| {
+            solution = Some(operators.to_vec());
+            ControlFlow::Break(())
+        });
+        solution
+    }
+
+    /// Like [`Solver::solutions`], but searches like [`Solver::solve_reverse`].
+    pub fn solutions_reverse<T>(&self, target: T, numbers: &[T]) -> Vec<Vec<O>>
+    where
+        T: Copy + Eq,
+        O: Invertible<T>,
+    {
+        let mut solutions = vec![];
+        let mut operators = Vec::with_capacity(numbers.len());
+        let _ = self.backward(target, numbers, &mut operators, &mut |operators| {
+            solutions.push(operators.to_vec());
+            ControlFlow::Continue(())
+        });
+        solutions
+    }
+
+    /// Chooses the operators from right to left, `operators` holds them in reverse.
+    fn backward<T>(
+        &self,
+        target: T,
+        numbers: &[T],
+        operators: &mut Vec<O>,
+        found: &mut impl FnMut(&[O]) -> ControlFlow<()>,
+    ) -> ControlFlow<()>
+    where
+        T: Copy + Eq,
+        O: Invertible<T>,
+    {
+        match numbers {
+            [] => ControlFlow::Continue(()),
+            [first] if *first == target => {
+                let in_order: Vec<O> = operators.iter().rev().cloned().collect();
+                found(&in_order)
+            }
+            [_] => ControlFlow::Continue(()),
+            [rest @ .., y] => {
+                for operator in self.operators {
+                    operators.push(operator.clone());
+                    if operator.absorbs(target, *y) {
+                        // the numbers before `y` may evaluate to anything
+                        self.absorbed(rest, rest.len() - 1, operators, found)?;
+                    } else if let Some(target) = operator.invert(target, *y) {
+                        self.backward(target, rest, operators, found)?;
+                    }
+                    operators.pop();
+                }
+
+                ControlFlow::Continue(())
+            }
+        }
+    }
+
+    /// Chooses the `remaining` operators between `numbers` in every way that evaluates, `operators` holds them in
+    /// reverse.
+    fn absorbed<T>(
+        &self,
+        numbers: &[T],
+        remaining: usize,
+        operators: &mut Vec<O>,
+        found: &mut impl FnMut(&[O]) -> ControlFlow<()>,
+    ) -> ControlFlow<()>
+    where
+        T: Copy + Eq,
+        O: Invertible<T>,
+    {
+        if remaining == 0 {
+            let in_order: Vec<O> = operators.iter().rev().cloned().collect();
+            let prefix = &in_order[..numbers.len() - 1];
+            return if evaluate(numbers, prefix, Evaluation::LeftToRight).is_some() {
+                found(&in_order)
+            } else {
+                ControlFlow::Continue(())
+            };
+        }
+
+        for operator in self.operators {
+            operators.push(operator.clone());
+            self.absorbed(numbers, remaining - 1, operators, found)?;
+            operators.pop();
+        }
+
+        ControlFlow::Continue(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Operators on unsigned integers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Arithmetic {
+    Add,
+    Multiply,
+    /// Appends the digits of the right number to the left one, e.g. `12 || 345 = 12345`.
+    Concatenate,
+}
+
+impl Arithmetic {
+    pub const ALL: [Self; 3] = [Self::Add, Self::Multiply, Self::Concatenate];
+}
+
+/// `10^digits` of `y`, with `0` having one digit.
+fn digit_shift(y: u64) -> Option<u64> {
+    10u64.checked_pow(y.checked_ilog10().unwrap_or(0) + 1)
+}
+
+impl Operator<u64> for Arithmetic {
+    fn apply(&self, x: u64, y: u64) -> Option<u64> {
+        match self {
+            Self::Add => x.checked_add(y),
+            Self::Multiply => x.checked_mul(y),
+            Self::Concatenate => x.checked_mul(digit_shift(y)?)?.checked_add(y),
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Add => 1,
+            Self::Multiply => 2,
+            Self::Concatenate => 3,
+        }
+    }
+
+    fn grows(&self, y: u64) -> bool {
+        match self {
+            Self::Add | Self::Concatenate => true,
+            Self::Multiply => y != 0,
+        }
+    }
+}
+
+impl Invertible<u64> for Arithmetic {
+    fn invert(&self, result: u64, y: u64) -> Option<u64> {
+        match self {
+            Self::Add => result.checked_sub(y),
+            Self::Multiply => (y != 0 && result.is_multiple_of(y)).then(|| result / y),
+            Self::Concatenate => {
+                let shift = digit_shift(y)?;
+                (result % shift == y).then(|| result / shift)
+            }
+        }
+    }
+    fn absorbs(&self, result: u64, y: u64) -> bool {
+        *self == Self::Multiply && y == 0 && result == 0
+    }
+}
+
+impl Display for Arithmetic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Add => "+",
+            Self::Multiply => "*",
+            Self::Concatenate => "||",
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{evaluate, Arithmetic, Evaluation, Operator, Solver};
+
+    use Arithmetic::{Add, Concatenate, Multiply};
+
+    #[test]
+    fn evaluates_expressions() {
+        let numbers = [2, 3, 4];
+        assert_eq!(
+            evaluate(&numbers, &[Add, Multiply], Evaluation::LeftToRight),
+            Some(20)
+        );
+        assert_eq!(
+            evaluate(&numbers, &[Add, Multiply], Evaluation::Precedence),
+            Some(14)
+        );
+        assert_eq!(
+            evaluate(&numbers, &[Multiply, Add], Evaluation::Precedence),
+            Some(10)
+        );
+        assert_eq!(
+            evaluate(&numbers, &[Add, Concatenate], Evaluation::Precedence),
+            Some(36)
+        );
+        assert_eq!(
+            evaluate(&[u64::MAX, 2], &[Add], Evaluation::LeftToRight),
+            None
+        );
+    }
+
+    #[test]
+    fn solves_equations() {
+        let simple = Solver::new(&[Add, Multiply]);
+        assert_eq!(simple.solve(190, &[10, 19]), Some(vec![Multiply]));
+        assert_eq!(simple.solve(7290, &[6, 8, 6, 15]), None);
+        assert_eq!(
+            simple.solutions(3267, &[81, 40, 27]),
+            [vec![Add, Multiply], vec![Multiply, Add]]
+        );
+
+        let all = Solver::new(&Arithmetic::ALL);
+        assert_eq!(
+            all.solve(7290, &[6, 8, 6, 15]),
+            Some(vec![Multiply, Concatenate, Multiply])
+        );
+        assert_eq!(
+            all.solve_reverse(7290, &[6, 8, 6, 15]),
+            all.solve(7290, &[6, 8, 6, 15])
+        );
+        assert_eq!(all.solutions_reverse(3267, &[81, 40, 27]).len(), 2);
+        assert_eq!(all.solve_reverse(156, &[15, 6]), Some(vec![Concatenate]));
+        assert_eq!(all.solve_reverse(83, &[17, 5]), None);
+
+        // multiplying by 0 shrinks any result, and every number before it works
+        assert_eq!(simple.solve(0, &[5, 3, 0]), Some(vec![Add, Multiply]));
+        assert_eq!(all.solutions(0, &[5, 3, 0]).len(), 3);
+        assert_eq!(all.solve_reverse(0, &[5, 3, 0]), Some(vec![Add, Multiply]));
+        assert_eq!(all.solutions_reverse(0, &[5, 3, 0]).len(), 3);
+        assert_eq!(all.solve_reverse(4, &[5, 0, 4]), Some(vec![Multiply, Add]));
+
+        let precedence = simple.with_evaluation(Evaluation::Precedence);
+        assert_eq!(precedence.solve(14, &[2, 3, 4]), Some(vec![Add, Multiply]));
+    }
+
+    /// Subtraction, to check user-defined operators that can shrink the result.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Subtract;
+
+    impl Operator<u64> for Subtract {
+        fn apply(&self, x: u64, y: u64) -> Option<u64> {
+            x.checked_sub(y)
+        }
+    }
+
+    #[test]
+    fn solves_with_custom_operators() {
+        let solver = Solver::new(&[Subtract]);
+        assert_eq!(solver.solve(5, &[10, 3, 2]), Some(vec![Subtract, Subtract]));
+        assert_eq!(solver.solve(5, &[2, 3]), None);
+    }
+}
@@ -0,0 +1,401 @@
+//! Directions, points and distances on a 2d plane.
+//!
+//! Like [`Grid`](crate::grid::Grid), the plane uses screen coordinates: `x` grows to the east and `y` grows to the
+//! south, so [`Direction::North`] is the offset `(0, -1)`.
+//!
+//! ```
+//! use advent_of_code::geometry::{self, Direction};
+//! use advent_of_code::grid::Grid;
+//!
+//! let grid: Grid<char> = "..#\n...".parse().unwrap();
+//! let facing = Direction::West.turn_right();
+//! assert_eq!(facing, Direction::North);
+//! assert_eq!(grid.get_offset((2, 1), facing), Some(&'#'));
+//! assert_eq!(geometry::checked_add((0, 0), facing), None);
+//! ```
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::grid::{Offset, Position};
+
+/// One of the 4 orthogonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// All directions, clockwise starting north.
+    pub const ALL: [Direction; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    pub fn iter() -> impl Iterator<Item = Self> {
+        Self::ALL.into_iter()
+    }
+
+    /// The offset of a single step in this direction.
+    pub fn offset(self) -> Offset {
+        match self {
+            Self::North => (0, -1),
+            Self::East => (1, 0),
+            Self::South => (0, 1),
+            Self::West => (-1, 0),
+        }
+    }
+
+    /// Turns clockwise by 90 degrees.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    /// Turns counterclockwise by 90 degrees.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    /// Turns by 180 degrees.
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// Returns `true` for east and west.
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Self::East | Self::West)
+    }
+
+    /// Parses arrows (`^>v<`) and the initials of directions (`NESW`, `URDL`).
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' | 'N' | 'U' => Some(Self::North),
+            '>' | 'E' | 'R' => Some(Self::East),
+            'v' | 'S' | 'D' => Some(Self::South),
+            '<' | 'W' | 'L' => Some(Self::West),
+            _ => None,
+        }
+    }
+
+    /// The arrow pointing in this direction, e.g. `^` for north.
+    pub fn arrow(self) -> char {
+        match self {
+            Self::North => '^',
+            Self::East => '>',
+            Self::South => 'v',
+            Self::West => '<',
+        }
+    }
+}
+
+impl From<Direction> for Offset {
+    fn from(direction: Direction) -> Self {
+        direction.offset()
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.arrow())
+    }
+}
+
+/// One of the 8 orthogonal and diagonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// All directions, clockwise starting north.
+    pub const ALL: [Direction8; 8] = [
+        Self::North,
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::South,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
+    pub fn iter() -> impl Iterator<Item = Self> {
+        Self::ALL.into_iter()
+    }
+
+    /// The offset of a single step in this direction.
+    pub fn offset(self) -> Offset {
+        match self {
+            Self::North => (0, -1),
+            Self::NorthEast => (1, -1),
+            Self::East => (1, 0),
+            Self::SouthEast => (1, 1),
+            Self::South => (0, 1),
+            Self::SouthWest => (-1, 1),
+            Self::West => (-1, 0),
+            Self::NorthWest => (-1, -1),
+        }
+    }
+
+    /// Turns clockwise by 45 degrees.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Turns counterclockwise by 45 degrees.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    /// Turns by 180 degrees.
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    /// Returns `true` for the 4 diagonal directions.
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        Self::ALL[direction as usize * 2]
+    }
+}
+
+impl From<Direction8> for Offset {
+    fn from(direction: Direction8) -> Self {
+        direction.offset()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A point, or vector, on an unbounded plane.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    /// The Manhattan (taxicab) distance, i.e. the number of orthogonal steps between the points.
+    pub fn manhattan(self, other: Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// The Chebyshev distance, i.e. the number of orthogonal or diagonal steps between the points.
+    pub fn chebyshev(self, other: Self) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Rotates clockwise by 90 degrees around the origin.
+    #[must_use]
+    pub fn rotate_right(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// Rotates counterclockwise by 90 degrees around the origin.
+    #[must_use]
+    pub fn rotate_left(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    /// Mirrors at the vertical axis through the origin.
+    #[must_use]
+    pub fn reflect_x(self) -> Self {
+        Self::new(-self.x, self.y)
+    }
+
+    /// Mirrors at the horizontal axis through the origin.
+    #[must_use]
+    pub fn reflect_y(self) -> Self {
+        Self::new(self.x, -self.y)
+    }
+
+    /// The point as a grid position, if both coordinates are non-negative.
+    pub fn to_position(self) -> Option<Position> {
+        Some((usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?))
+    }
+
+    /// The grid position as a point, if both coordinates fit.
+    pub fn from_position((x, y): Position) -> Option<Self> {
+        Some(Self::new(
+            isize::try_from(x).ok()?,
+            isize::try_from(y).ok()?,
+        ))
+    }
+}
+
+impl From<Offset> for Point {
+    fn from((x, y): Offset) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl From<Point> for Offset {
+    fn from(point: Point) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl From<Direction> for Point {
+    fn from(direction: Direction) -> Self {
+        direction.offset().into()
+    }
+}
+
+impl From<Direction8> for Point {
+    fn from(direction: Direction8) -> Self {
+        direction.offset().into()
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<isize> for Point {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Point {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Moves a grid position by an offset, returns [`None`] if a coordinate would become negative or overflow.
+///
+/// Unlike [`Grid::offset`](crate::grid::Grid::offset), this does not check an upper bound.
+pub fn checked_add((x, y): Position, offset: impl Into<Offset>) -> Option<Position> {
+    let (dx, dy) = offset.into();
+    Some((x.checked_add_signed(dx)?, y.checked_add_signed(dy)?))
+}
+
+/// Moves a grid position `steps` times by an offset, see [`checked_add`].
+pub fn checked_add_n(
+    position: Position,
+    offset: impl Into<Offset>,
+    steps: usize,
+) -> Option<Position> {
+    let (dx, dy) = offset.into();
+    let steps = isize::try_from(steps).ok()?;
+    checked_add(position, (dx.checked_mul(steps)?, dy.checked_mul(steps)?))
+}
+
+/// The Manhattan distance between grid positions, see [`Point::manhattan`].
+pub fn manhattan((x1, y1): Position, (x2, y2): Position) -> usize {
+    x1.abs_diff(x2) + y1.abs_diff(y2)
+}
+
+/// The Chebyshev distance between grid positions, see [`Point::chebyshev`].
+pub fn chebyshev((x1, y1): Position, (x2, y2): Position) -> usize {
+    x1.abs_diff(x2).max(y1.abs_diff(y2))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns_directions() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::East.reverse(), Direction::West);
+        assert_eq!(Direction8::North.turn_left(), Direction8::NorthWest);
+        assert_eq!(Direction8::SouthEast.reverse(), Direction8::NorthWest);
+        assert_eq!(Direction8::from(Direction::South), Direction8::South);
+        assert!(Direction8::NorthEast.is_diagonal());
+
+        for direction in Direction::iter() {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(Direction::from_char(direction.arrow()), Some(direction));
+            // turning the direction matches rotating its offset
+            assert_eq!(
+                Point::from(direction.turn_right()),
+                Point::from(direction).rotate_right()
+            );
+        }
+    }
+
+    #[test]
+    fn calculates_with_points() {
+        let a = Point::new(1, -2);
+        let b = Point::new(-3, 4);
+        assert_eq!(a + b, Point::new(-2, 2));
+        assert_eq!(a - b, Point::new(4, -6));
+        assert_eq!(a * 3, Point::new(3, -6));
+        assert_eq!(-a, Point::new(-1, 2));
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!(a.reflect_x(), Point::new(-1, -2));
+        assert_eq!(a.reflect_y(), Point::new(1, 2));
+        assert_eq!(a.rotate_left().rotate_right(), a);
+        assert_eq!(a.to_position(), None);
+        assert_eq!(b.reflect_x().to_position(), Some((3, 4)));
+    }
+
+    #[test]
+    fn moves_positions() {
+        assert_eq!(checked_add((1, 1), Direction::North), Some((1, 0)));
+        assert_eq!(checked_add((1, 0), Direction8::NorthEast), None);
+        assert_eq!(checked_add_n((0, 5), Direction::South, 3), Some((0, 8)));
+        assert_eq!(checked_add_n((2, 5), Direction::West, 3), None);
+        assert_eq!(manhattan((0, 0), (3, 4)), 7);
+        assert_eq!(chebyshev((0, 0), (3, 4)), 4);
+    }
+}
@@ -0,0 +1,390 @@
+//! A dense, rectangular grid of cells, e.g. the map of a puzzle.
+//!
+//! Positions are `(x, y)` pairs, where `x` is the column and `y` the row, both starting at the top left corner.
+//! Offsets are signed `(dx, dy)` pairs, e.g. `(1, -1)` moves to the top right.
+//!
+//! ```
+//! use advent_of_code::grid::Grid;
+//!
+//! let grid: Grid<char> = "..#\n#..".parse().unwrap();
+//! assert_eq!(grid[(2, 0)], '#');
+//! assert_eq!(grid.find(|&c| c == '#'), Some((2, 0)));
+//! assert_eq!(grid.get_offset((0, 1), (0, 1)), None);
+//! ```
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::geometry;
+use crate::parse::{self, LineError, LineErrorKind, Section};
+use crate::template::error::InputError;
+
+/// A position in a grid as `(x, y)`.
+pub type Position = (usize, usize);
+/// A signed offset between positions as `(dx, dy)`.
+pub type Offset = (isize, isize);
+
+/// Offsets to the 4 orthogonal neighbours, clockwise starting north.
+pub const NEIGHBOURS_4: [Offset; 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+/// Offsets to the 8 orthogonal and diagonal neighbours, clockwise starting north.
+pub const NEIGHBOURS_8: [Offset; 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    /// Cells in row-major order.
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid of `width` x `height` cells, all set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Creates a grid from its rows, returns [`None`] if the rows differ in length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Self {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses every character of the input (or of a [`Section`]) with `f`. All lines need to have the same length.
+    pub fn parse_with<'a, E>(
+        input: impl Into<Section<'a>>,
+        mut f: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, InputError>
+    where
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        parse::rectangular(input, |line| {
+            line.chars()
+                .enumerate()
+                .map(|(i, c)| {
+                    f(c).map_err(|e| {
+                        LineError::new(LineErrorKind::InvalidValue {
+                            found: c.into(),
+                            source: e.into(),
+                        })
+                        .at_column(i + 1)
+                    })
+                })
+                .collect()
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns `true` if the position is within the grid.
+    pub fn contains(&self, (x, y): Position) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.index_of(position).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.index_of(position).map(|i| &mut self.cells[i])
+    }
+
+    /// Moves from `position` by `offset` (e.g. a [`Direction`](crate::geometry::Direction)),
+    /// returns [`None`] if that leaves the grid.
+    pub fn offset(&self, position: Position, offset: impl Into<Offset>) -> Option<Position> {
+        let position = geometry::checked_add(position, offset)?;
+        self.contains(position).then_some(position)
+    }
+
+    /// The cell at `offset` from `position`, if it is within the grid.
+    pub fn get_offset(&self, position: Position, offset: impl Into<Offset>) -> Option<&T> {
+        self.offset(position, offset).and_then(|p| self.get(p))
+    }
+
+    /// The positions of the orthogonal neighbours within the grid.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        NEIGHBOURS_4
+            .into_iter()
+            .filter_map(move |offset| self.offset(position, offset))
+    }
+
+    /// The positions of the orthogonal and diagonal neighbours within the grid.
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        NEIGHBOURS_8
+            .into_iter()
+            .filter_map(move |offset| self.offset(position, offset))
+    }
+
+    /// The positions from `position` (inclusive) in steps of `offset` until the edge of the grid.
+    pub fn ray(
+        &self,
+        position: Position,
+        offset: impl Into<Offset>,
+    ) -> impl Iterator<Item = Position> + '_ {
+        let offset = offset.into();
+        let start = self.contains(position).then_some(position);
+        std::iter::successors(start, move |&p| self.offset(p, offset))
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All cells with their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // NOTE: `chunks` panics on a chunk size of 0.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `x` is not within the width, like [`Grid::row`] does for `y`.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is outside of the grid");
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The diagonals from the top left to the bottom right (`\`), starting at the bottom left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.non_empty_height())
+            .rev()
+            .map(|y| (0, y))
+            .chain((1..self.width).map(|x| (x, 0)));
+        starts.map(|start| self.ray(start, (1, 1)).map(|p| &self[p]))
+    }
+
+    /// The diagonals from the top right to the bottom left (`/`), starting at the top left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.width)
+            .map(|x| (x, 0))
+            .chain((1..self.non_empty_height()).map(|y| (self.width - 1, y)));
+        starts.map(|start| self.ray(start, (-1, 1)).map(|p| &self[p]))
+    }
+
+    /// The height, or 0 if the rows are empty. Grids like `Grid::parse("\n\n")` have empty rows but no diagonals.
+    fn non_empty_height(&self) -> usize {
+        if self.width == 0 {
+            0
+        } else {
+            self.height
+        }
+    }
+
+    /// The position of the first cell in row-major order that matches the predicate.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Position> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    /// The positions of all cells that match the predicate in row-major order.
+    pub fn find_all<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Position> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    /// Converts every cell with `f`.
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.into_iter().map(f).collect(),
+        }
+    }
+
+    fn index_of(&self, (x, y): Position) -> Option<usize> {
+        self.contains((x, y)).then(|| y * self.width + x)
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    /// # Panics
+    /// If the position is outside of the grid.
+    fn index(&self, position: Position) -> &Self::Output {
+        self.get(position)
+            .unwrap_or_else(|| panic!("position {position:?} should be within the grid"))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("position {position:?} should be within the grid"))
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = InputError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse::char_grid(s)
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Grid;
+
+    fn get_mock_grid() -> Grid<char> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = get_mock_grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 1)], 'e');
+        assert_eq!(grid.to_string(), "abc\ndef");
+
+        let error = Grid::parse_with("12\n3x", |c| c.to_string().parse::<u8>()).unwrap_err();
+        assert_eq!((error.line, error.column), (2, Some(2)));
+        assert!("ab\nc".parse::<Grid<char>>().is_err());
+        assert_eq!(Grid::from_rows(vec![vec![1], vec![2, 3]]), None);
+    }
+
+    #[test]
+    fn checks_bounds() {
+        let mut grid = get_mock_grid();
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get_offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.get_offset((0, 0), (2, 1)), Some(&'f'));
+        assert_eq!(grid.offset((2, 1), (1, 0)), None);
+
+        grid[(0, 0)] = 'x';
+        assert_eq!(grid.row(0), &['x', 'b', 'c']);
+    }
+
+    #[test]
+    fn iterates_neighbours() {
+        let grid = get_mock_grid();
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours8((1, 0)).count(), 5);
+        assert_eq!(
+            grid.ray((0, 0), (1, 0)).collect::<Vec<_>>(),
+            vec![(0, 0), (1, 0), (2, 0)]
+        );
+        assert_eq!(grid.ray((5, 5), (1, 0)).count(), 0);
+    }
+
+    #[test]
+    fn iterates_lines() {
+        let grid = get_mock_grid();
+        let collect = |cells: &mut dyn Iterator<Item = &char>| cells.collect::<String>();
+
+        assert_eq!(
+            grid.columns()
+                .map(|mut c| collect(&mut c))
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+        assert_eq!(
+            grid.diagonals()
+                .map(|mut d| collect(&mut d))
+                .collect::<Vec<_>>(),
+            vec!["d", "ae", "bf", "c"]
+        );
+        assert_eq!(
+            grid.anti_diagonals()
+                .map(|mut d| collect(&mut d))
+                .collect::<Vec<_>>(),
+            vec!["a", "bd", "ce", "f"]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_columns_outside_of_the_grid() {
+        let grid = get_mock_grid();
+        let _ = grid.column(3);
+    }
+
+    #[test]
+    fn iterates_lines_of_empty_rows() {
+        let grid: Grid<char> = "\n\n".parse().unwrap();
+        assert_eq!((grid.width(), grid.height()), (0, 2));
+        assert_eq!(grid.columns().count(), 0);
+        assert_eq!(grid.diagonals().count(), 0);
+        assert_eq!(grid.anti_diagonals().count(), 0);
+    }
+
+    #[test]
+    fn finds_cells() {
+        let grid: Grid<char> = "#.#\n..#".parse().unwrap();
+        assert_eq!(grid.find(|&c| c == '#'), Some((0, 0)));
+        assert_eq!(
+            grid.find_all(|&c| c == '#').collect::<Vec<_>>(),
+            vec![(0, 0), (2, 0), (2, 1)]
+        );
+        assert_eq!(grid.find(|&c| c == 'x'), None);
+        assert_eq!(grid.map(|c| c == '#').iter().filter(|(_, &b)| b).count(), 3);
+    }
+}
@@ -1,3 +1,12 @@
+pub mod cycle;
+pub mod equation;
+pub mod geometry;
+pub mod grid;
+pub mod order;
+pub mod parse;
+pub mod replay;
+pub mod search;
 pub mod template;
+pub mod viz;
 
 // Use this file to add helper functions and additional modules.
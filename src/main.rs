@@ -1,40 +1,71 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{all, download, read, replay, scaffold, solve, time};
+use advent_of_code::template::logging;
+use advent_of_code::template::registry::Executor;
 use args::{parse, AppArguments};
 
+/// Every solution in `src/bin/`, generated by `build.rs`.
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::{Day, PuzzleId, Year};
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        commands::{replay::ReplayOptions, solve::SolveOptions},
+        input::InputSource,
+        record::OutputFormat,
+        runner::BenchConfig,
+        timeout::{TimeoutRule, Timeouts},
+        Day, PuzzleId, Year,
+    };
+    use advent_of_code::viz;
+    use std::{fmt::Display, path::PathBuf, process, str::FromStr, time::Duration};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
             overwrite: bool,
         },
         Solve {
-            day: Day,
-            release: bool,
-            dhat: bool,
-            submit: Option<u8>,
+            puzzle: PuzzleId,
+            options: SolveOptions,
         },
         All {
+            year: Year,
             release: bool,
+            isolated: bool,
+            format: OutputFormat,
+            jobs: usize,
+            timeouts: Timeouts,
         },
         Time {
+            year: Year,
             all: bool,
             day: Option<Day>,
             store: bool,
+            format: OutputFormat,
+            bench: BenchConfig,
+            compare: Option<f64>,
+            isolated: bool,
+        },
+        History {
+            puzzle: PuzzleId,
+            format: OutputFormat,
+        },
+        Replay {
+            puzzle: PuzzleId,
+            options: ReplayOptions,
         },
         #[cfg(feature = "today")]
         Today,
@@ -45,35 +76,118 @@ mod args {
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
+                year: year(&mut args)?,
                 release: args.contains("--release"),
+                isolated: args.contains("--isolated"),
+                format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                timeouts: Timeouts::new(args.values_from_str::<_, TimeoutRule>("--timeout")?),
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let compare = args.contains("--compare");
+                let isolated = args.contains("--isolated");
+                let threshold: f64 = args.opt_value_from_str("--threshold")?.unwrap_or(5.0);
+                let format = args.opt_value_from_str("--format")?.unwrap_or_default();
+                let year = year(&mut args)?;
+
+                if let Some(day) = args.opt_value_from_str("--history")? {
+                    let puzzle = PuzzleId::new(year, day);
+                    return finish(args, AppArguments::History { puzzle, format });
+                }
+
+                let default = BenchConfig::default();
+                let bench = BenchConfig {
+                    warmup: args
+                        .opt_value_from_str("--warmup")?
+                        .unwrap_or(default.warmup),
+                    target_time: args
+                        .opt_value_from_str("--target-time")?
+                        .map_or(default.target_time, Duration::from_millis),
+                };
 
                 AppArguments::Time {
+                    year,
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    format,
+                    bench,
+                    compare: compare.then_some(threshold / 100.0),
+                    isolated,
                 }
             }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: puzzle(&mut args)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: puzzle(&mut args)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                puzzle: puzzle(&mut args)?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
+            Some("solve") => {
+                let mut options = SolveOptions {
+                    release: args.contains("--release"),
+                    submit: args.opt_value_from_str("--submit")?,
+                    dhat: args.contains("--dhat"),
+                    accept: args.contains("--accept"),
+                    format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                    viz: if args.contains("--viz-frames") {
+                        Some(viz::Output::Frames)
+                    } else if args.contains("--viz") {
+                        Some(viz::Output::Terminal)
+                    } else {
+                        None
+                    },
+                    verbose: args.contains("--verbose"),
+                    watch: args.contains("--watch"),
+                    input: InputSource::Puzzle,
+                };
+
+                let path: Option<PathBuf> = args.opt_value_from_str("--input")?;
+                if args.contains("--example") {
+                    if path.is_some() {
+                        eprintln!("`--input` and `--example` can not be combined.");
+                        process::exit(1);
+                    }
+
+                    let (puzzle, number) = puzzle_with_example(&mut args)?;
+                    options.input = InputSource::Example(number);
+                    return finish(args, AppArguments::Solve { puzzle, options });
+                }
+
+                options.input = match path {
+                    Some(path) if path.as_os_str() == "-" => InputSource::Stdin,
+                    Some(path) => InputSource::File(path),
+                    None => InputSource::Puzzle,
+                };
+
+                AppArguments::Solve {
+                    options,
+                    puzzle: puzzle(&mut args)?,
+                }
+            }
+            Some("replay") => {
+                let default = ReplayOptions::default();
+                let options = ReplayOptions {
+                    fps: args.opt_value_from_str("--fps")?.unwrap_or(default.fps),
+                    every: args
+                        .opt_value_from_str::<_, usize>("--every")?
+                        .map_or(default.every, |every| every.max(1)),
+                    gif: args.contains("--gif"),
+                    scale: args.opt_value_from_str("--scale")?.unwrap_or(default.scale),
+                    no_run: args.contains("--no-run"),
+                };
+
+                AppArguments::Replay {
+                    options,
+                    puzzle: puzzle(&mut args)?,
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -86,6 +200,69 @@ mod args {
             }
         };
 
+        finish(args, app_args)
+    }
+
+    /// Reads the `--year` option, defaulting to the configured year.
+    fn year(args: &mut pico_args::Arguments) -> Result<Year, pico_args::Error> {
+        Ok(args
+            .opt_value_from_str("--year")?
+            .unwrap_or_else(Year::configured))
+    }
+
+    /// Reads the free arguments `[YEAR] DAY`, e.g. `2023 5` or `5` for the configured year.
+    fn puzzle(args: &mut pico_args::Arguments) -> Result<PuzzleId, pico_args::Error> {
+        let first: String = args.free_from_str()?;
+
+        let (year, day) = match args.opt_free_from_str::<Day>()? {
+            Some(day) => (parse_free(&first)?, day),
+            None => (Year::configured(), parse_free(&first)?),
+        };
+
+        Ok(PuzzleId::new(year, day))
+    }
+
+    /// Reads the free arguments `[YEAR] DAY [N]` of `--example [N]`, e.g. `5 2` for the second example of day 5.
+    fn puzzle_with_example(
+        args: &mut pico_args::Arguments,
+    ) -> Result<(PuzzleId, Option<u8>), pico_args::Error> {
+        let first: String = args.free_from_str()?;
+        let second: Option<String> = args.opt_free_from_str()?;
+        let third: Option<String> = args.opt_free_from_str()?;
+
+        // both `YEAR DAY` and `DAY N` are two arguments, only the former starts with a year
+        let (year, day, number) = match (second, third) {
+            (Some(day), Some(number)) => (Some(first), day, Some(number)),
+            (Some(day), None) if first.parse::<Year>().is_ok() => (Some(first), day, None),
+            (Some(number), None) => (None, first, Some(number)),
+            (None, _) => (None, first, None),
+        };
+
+        let year = match year {
+            Some(year) => parse_free(&year)?,
+            None => Year::configured(),
+        };
+        let number = number.as_deref().map(parse_free).transpose()?;
+
+        Ok((PuzzleId::new(year, parse_free(&day)?), number))
+    }
+
+    fn parse_free<T: FromStr>(value: &str) -> Result<T, pico_args::Error>
+    where
+        T::Err: Display,
+    {
+        value
+            .parse()
+            .map_err(|e: T::Err| pico_args::Error::Utf8ArgumentParsingFailed {
+                value: value.to_string(),
+                cause: e.to_string(),
+            })
+    }
+
+    fn finish(
+        args: pico_args::Arguments,
+        app_args: AppArguments,
+    ) -> Result<AppArguments, Box<dyn std::error::Error>> {
         let remaining = args.finish();
         if !remaining.is_empty() {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
@@ -95,40 +272,75 @@ mod args {
     }
 }
 
+/// Runs solutions in-process unless `--isolated` is passed.
+fn executor(isolated: bool, is_release: bool) -> Executor<'static> {
+    if isolated {
+        Executor::Isolated { is_release }
+    } else {
+        Executor::InProcess(solutions::ALL)
+    }
+}
+
 fn main() {
+    // solutions that run in-process log with the filters of `RUST_LOG`
+    logging::init(env!("CARGO_CRATE_NAME"));
+
     match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
+            AppArguments::All {
+                year,
+                release,
+                isolated,
+                format,
+                jobs,
+                timeouts,
+            } => all::handle(year, executor(isolated, release), format, jobs, &timeouts),
+            AppArguments::Time {
+                year,
+                day,
+                all,
+                store,
+                format,
+                bench,
+                compare,
+                isolated,
+            } => time::handle(
+                year,
                 day,
+                all,
+                store,
+                format,
+                bench,
+                compare,
+                executor(isolated, true),
+            ),
+            AppArguments::History { puzzle, format } => time::handle_history(puzzle, format),
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Scaffold {
+                puzzle,
                 download,
                 overwrite,
             } => {
-                scaffold::handle(day, overwrite);
+                scaffold::handle(puzzle, overwrite);
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
                 }
             }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
+            AppArguments::Solve { puzzle, options } => solve::handle(puzzle, options),
+            AppArguments::Replay { puzzle, options } => replay::handle(puzzle, options),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                        let puzzle = PuzzleId::new(Year::configured(), day);
+                        scaffold::handle(puzzle, false);
+                        download::handle(puzzle);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
@@ -0,0 +1,421 @@
+//! Partial orders given by constraints "`a` comes before `b`", e.g. page ordering rules or task dependencies.
+//!
+//! The constraints form a directed graph. As long as it has no cycles (i.e. it is a DAG) the elements can be sorted
+//! topologically, and sequences can be checked against the constraints:
+//!
+//! ```
+//! use advent_of_code::order::PartialOrder;
+//!
+//! let mut order = PartialOrder::new();
+//! order.insert(1, 2);
+//! order.insert(2, 3);
+//!
+//! assert!(order.precedes(&1, &3));
+//! assert!(!order.is_consistent(&[2, 1]));
+//! assert_eq!(order.topological_sort().unwrap(), [1, 2, 3]);
+//! ```
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+
+use crate::search;
+
+/// A constraint that `before` comes before `after`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Constraint<T> {
+    pub before: T,
+    pub after: T,
+}
+
+/// A constraint that contradicts the others, i.e. it is part of a cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError<T>(pub Constraint<T>);
+
+impl<T: Debug> Display for CycleError<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Constraint { before, after } = &self.0;
+        write!(
+            f,
+            "the constraint {before:?} before {after:?} is part of a cycle"
+        )
+    }
+}
+
+impl<T: Debug> Error for CycleError<T> {}
+
+/* -------------------------------------------------------------------------- */
+
+/// A set of elements with constraints on their order.
+///
+/// Elements are kept in the order they were first mentioned, which breaks ties when sorting.
+#[derive(Debug, Clone)]
+pub struct PartialOrder<T> {
+    elements: Vec<T>,
+    indices: HashMap<T, usize>,
+    /// The elements directly after and before each element, by index.
+    after: Vec<HashSet<usize>>,
+    before: Vec<HashSet<usize>>,
+}
+
+impl<T: Clone + Eq + Hash> PartialOrder<T> {
+    pub fn new() -> Self {
+        Self {
+            elements: vec![],
+            indices: HashMap::new(),
+            after: vec![],
+            before: vec![],
+        }
+    }
+
+    /// Adds an element without any constraints, returns its index.
+    pub fn add(&mut self, element: T) -> usize {
+        if let Some(&index) = self.indices.get(&element) {
+            return index;
+        }
+
+        let index = self.elements.len();
+        self.indices.insert(element.clone(), index);
+        self.elements.push(element);
+        self.after.push(HashSet::new());
+        self.before.push(HashSet::new());
+        index
+    }
+
+    /// Adds the constraint that `before` comes before `after`, returns `false` if it was known already.
+    ///
+    /// The constraint is added even if it creates a cycle, see [`PartialOrder::try_insert`].
+    pub fn insert(&mut self, before: T, after: T) -> bool {
+        let before = self.add(before);
+        let after = self.add(after);
+
+        self.before[after].insert(before);
+        self.after[before].insert(after)
+    }
+
+    /// Like [`PartialOrder::insert`], but rejects a constraint that would create a cycle.
+    pub fn try_insert(&mut self, before: T, after: T) -> Result<bool, CycleError<T>> {
+        if before == after || self.precedes(&after, &before) {
+            return Err(CycleError(Constraint { before, after }));
+        }
+
+        Ok(self.insert(before, after))
+    }
+
+    /// The elements in the order they were first mentioned.
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn contains(&self, element: &T) -> bool {
+        self.indices.contains_key(element)
+    }
+
+    /// Every constraint, in arbitrary order.
+    pub fn constraints(&self) -> impl Iterator<Item = Constraint<&T>> {
+        self.after
+            .iter()
+            .enumerate()
+            .flat_map(move |(before, after)| {
+                after.iter().map(move |&after| Constraint {
+                    before: &self.elements[before],
+                    after: &self.elements[after],
+                })
+            })
+    }
+
+    /// The elements that are directly constrained to come after `element`.
+    pub fn after(&self, element: &T) -> impl Iterator<Item = &T> {
+        self.neighbours(element, &self.after)
+    }
+
+    /// The elements that are directly constrained to come before `element`.
+    pub fn before(&self, element: &T) -> impl Iterator<Item = &T> {
+        self.neighbours(element, &self.before)
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        element: &T,
+        edges: &'a [HashSet<usize>],
+    ) -> impl Iterator<Item = &'a T> {
+        self.indices
+            .get(element)
+            .into_iter()
+            .flat_map(move |&index| edges[index].iter().map(|&i| &self.elements[i]))
+    }
+
+    /* ------------------------------------------------------------------------ */
+
+    /// The elements that come after `element` directly or by transitivity.
+    pub fn all_after(&self, element: &T) -> HashSet<&T> {
+        self.reachable(element, &self.after)
+    }
+
+    /// The elements that come before `element` directly or by transitivity.
+    pub fn all_before(&self, element: &T) -> HashSet<&T> {
+        self.reachable(element, &self.before)
+    }
+
+    fn reachable(&self, element: &T, edges: &[HashSet<usize>]) -> HashSet<&T> {
+        let Some(&start) = self.indices.get(element) else {
+            return HashSet::new();
+        };
+
+        search::dfs(start, |&index| edges[index].iter().copied())
+            .skip(1)
+            .map(|index| &self.elements[index])
+            .collect()
+    }
+
+    /// Whether `a` comes before `b`, directly or by transitivity.
+    pub fn precedes(&self, a: &T, b: &T) -> bool {
+        let (Some(&a), Some(&b)) = (self.indices.get(a), self.indices.get(b)) else {
+            return false;
+        };
+
+        search::dfs(a, |&index| self.after[index].iter().copied())
+            .skip(1)
+            .any(|index| index == b)
+    }
+
+    /// Compares two elements by transitivity, [`None`] if they are not ordered.
+    ///
+    /// Elements on a cycle precede each other, they compare as [`Ordering::Less`].
+    pub fn compare(&self, a: &T, b: &T) -> Option<Ordering> {
+        if a == b {
+            Some(Ordering::Equal)
+        } else if self.precedes(a, b) {
+            Some(Ordering::Less)
+        } else if self.precedes(b, a) {
+            Some(Ordering::Greater)
+        } else {
+            None
+        }
+    }
+
+    /// The transitive closure, i.e. an order with a direct constraint for every pair of ordered elements.
+    pub fn closure(&self) -> Self {
+        let mut closure = self.restrict(&self.elements);
+        for element in &self.elements {
+            for after in self.all_after(element) {
+                closure.insert(element.clone(), after.clone());
+            }
+        }
+        closure
+    }
+
+    /// The order between `elements` only, in the given order. Constraints with other elements are dropped, so they do
+    /// not order `elements` by transitivity.
+    ///
+    /// This keeps a cycle through other elements from affecting an acyclic subset of the elements.
+    pub fn restrict<'a>(&self, elements: impl IntoIterator<Item = &'a T>) -> Self
+    where
+        T: 'a,
+    {
+        let mut restricted = Self::new();
+        for element in elements {
+            restricted.add(element.clone());
+        }
+
+        for (before, &index) in &restricted.indices.clone() {
+            for after in self.after(before) {
+                if restricted.contains(after) {
+                    restricted.after[index].insert(restricted.indices[after]);
+                    restricted.before[restricted.indices[after]].insert(index);
+                }
+            }
+        }
+
+        restricted
+    }
+
+    /* ------------------------------------------------------------------------ */
+
+    /// Sorts the elements so that every constraint holds, with Kahn's algorithm.
+    ///
+    /// Elements that are not ordered keep the order they were first mentioned in. Fails with a constraint on a cycle if
+    /// there is none.
+    pub fn topological_sort(&self) -> Result<Vec<T>, CycleError<T>> {
+        let mut in_degree: Vec<usize> = self.before.iter().map(HashSet::len).collect();
+        let mut ready: BinaryHeap<_> = (0..self.len())
+            .filter(|&index| in_degree[index] == 0)
+            .map(Reverse)
+            .collect();
+
+        let mut sorted = Vec::with_capacity(self.len());
+        while let Some(Reverse(index)) = ready.pop() {
+            sorted.push(self.elements[index].clone());
+
+            for &after in &self.after[index] {
+                in_degree[after] -= 1;
+                if in_degree[after] == 0 {
+                    ready.push(Reverse(after));
+                }
+            }
+        }
+
+        if sorted.len() < self.len() {
+            return Err(self.cycle_edge(&in_degree));
+        }
+
+        Ok(sorted)
+    }
+
+    /// Finds a constraint on a cycle among the elements that are left unsorted by Kahn's algorithm.
+    fn cycle_edge(&self, in_degree: &[usize]) -> CycleError<T> {
+        // every unsorted element has an unsorted element before it, so walking back has to repeat an element
+        let unsorted = |index: &usize| in_degree[*index] > 0;
+        let mut index = (0..self.len())
+            .find(unsorted)
+            .expect("an element is unsorted");
+        let mut seen = HashSet::from([index]);
+
+        loop {
+            let before = *self.before[index]
+                .iter()
+                .find(|before| unsorted(before))
+                .expect("unsorted elements have unsorted elements before them");
+
+            if !seen.insert(before) {
+                return CycleError(Constraint {
+                    before: self.elements[before].clone(),
+                    after: self.elements[index].clone(),
+                });
+            }
+            index = before;
+        }
+    }
+
+    /// The first constraint between elements of `sequence` that it violates, i.e. whose `after` comes first.
+    ///
+    /// Only direct constraints are checked, see [`PartialOrder::restrict`].
+    pub fn violation<'a>(&self, sequence: &'a [T]) -> Option<Constraint<&'a T>> {
+        let positions: HashMap<&T, usize> = sequence
+            .iter()
+            .enumerate()
+            .map(|(position, element)| (element, position))
+            .collect();
+
+        sequence.iter().enumerate().find_map(|(position, after)| {
+            self.before(after)
+                .filter_map(|before| positions.get(before))
+                .find(|&&before| before > position)
+                .map(|&before| Constraint {
+                    before: &sequence[before],
+                    after,
+                })
+        })
+    }
+
+    /// Whether `sequence` violates none of the constraints between its elements.
+    pub fn is_consistent(&self, sequence: &[T]) -> bool {
+        self.violation(sequence).is_none()
+    }
+}
+
+impl<T: Clone + Eq + Hash> Default for PartialOrder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone + Eq + Hash> FromIterator<(T, T)> for PartialOrder<T> {
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(constraints: I) -> Self {
+        let mut order = Self::new();
+        for (before, after) in constraints {
+            order.insert(before, after);
+        }
+        order
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::{Constraint, CycleError, PartialOrder};
+
+    /// 1 < 2 < 4, 1 < 3 < 4, 5 unordered
+    fn diamond() -> PartialOrder<u32> {
+        let mut order: PartialOrder<u32> = [(1, 2), (2, 4), (1, 3), (3, 4)].into_iter().collect();
+        order.add(5);
+        order
+    }
+
+    #[test]
+    fn queries_transitivity() {
+        let order = diamond();
+
+        assert!(order.precedes(&1, &4));
+        assert!(!order.precedes(&4, &1));
+        assert!(!order.precedes(&2, &3));
+        assert_eq!(order.compare(&4, &1), Some(Ordering::Greater));
+        assert_eq!(order.compare(&2, &3), None);
+        assert_eq!(order.compare(&5, &5), Some(Ordering::Equal));
+        assert_eq!(order.all_before(&4).len(), 3);
+        assert_eq!(order.closure().constraints().count(), 5);
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let mut order = diamond();
+        assert_eq!(
+            order.try_insert(4, 1),
+            Err(CycleError(Constraint {
+                before: 4,
+                after: 1
+            }))
+        );
+        assert_eq!(order.try_insert(2, 3), Ok(true));
+        assert_eq!(order.try_insert(2, 3), Ok(false));
+
+        // 1 < 2 < 3 < 4 < 2
+        order.insert(4, 2);
+        let CycleError(Constraint { before, after }) = order.topological_sort().unwrap_err();
+        assert!(order.after(&before).any(|&a| a == after));
+        assert!([2, 3, 4].contains(&before) && [2, 3, 4].contains(&after));
+    }
+
+    #[test]
+    fn sorts_topologically() {
+        let order = diamond();
+        assert_eq!(order.topological_sort().unwrap(), [1, 2, 3, 4, 5]);
+        assert_eq!(
+            order.restrict(&[5, 4, 3]).topological_sort().unwrap(),
+            [5, 3, 4]
+        );
+
+        // the cycle does not involve the restricted elements
+        let mut order = order;
+        order.insert(4, 1);
+        assert!(order.topological_sort().is_err());
+        assert_eq!(order.restrict(&[4, 2]).topological_sort().unwrap(), [2, 4]);
+    }
+
+    #[test]
+    fn checks_sequences() {
+        let order = diamond();
+
+        assert!(order.is_consistent(&[1, 3, 2, 4]));
+        assert!(order.is_consistent(&[5, 4]));
+        assert_eq!(
+            order.violation(&[1, 4, 2]),
+            Some(Constraint {
+                before: &2,
+                after: &4
+            })
+        );
+    }
+}
@@ -0,0 +1,413 @@
+//! Helpers to parse puzzle inputs.
+//!
+//! Line level helpers (e.g. [`integers`], [`split_once`]) return a [`LineError`] that points at the column of the
+//! malformed text. Input level helpers (e.g. [`lines_with`], [`sections_exact`]) add the line to it and return an
+//! [`InputError`], which is reported with a snippet of the input when returned by a solution:
+//!
+//! ```
+//! use advent_of_code::parse::{self, LineError};
+//! use advent_of_code::template::error::InputError;
+//!
+//! fn parse_equations(input: &str) -> Result<Vec<(u64, Vec<u64>)>, InputError> {
+//!     parse::lines_with(input, |line| -> Result<_, LineError> {
+//!         let (result, numbers) = parse::split_once(line, ":")?;
+//!         Ok((parse::field(line, result)?, parse::integers(numbers)?))
+//!     })
+//! }
+//!
+//! let error = parse_equations("190: 10 19\n3x67: 81 40 27\n").unwrap_err();
+//! assert_eq!((error.line, error.column), (2, Some(1)));
+//! ```
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::grid::Grid;
+use crate::template::error::InputError;
+
+/// An error caused by malformed text within a single line.
+#[derive(Debug)]
+pub struct LineError {
+    /// The column of the error in characters, starting at 1.
+    pub column: Option<usize>,
+    pub kind: LineErrorKind,
+}
+
+/// The reason a line could not be parsed.
+#[derive(Debug)]
+pub enum LineErrorKind {
+    /// The line does not contain the delimiter.
+    MissingDelimiter(String),
+    /// The text could not be parsed as a value.
+    InvalidValue {
+        found: String,
+        source: Box<dyn Error + Send + Sync>,
+    },
+    /// A character is not a decimal digit.
+    InvalidDigit(char),
+    /// The line contains another number of values than expected.
+    Count { expected: usize, found: usize },
+    /// The line contains less values than needed.
+    TooFew { min: usize, found: usize },
+}
+
+impl LineError {
+    pub fn new(kind: LineErrorKind) -> Self {
+        Self { column: None, kind }
+    }
+
+    /// Points the error at a column (in characters, starting at 1).
+    #[must_use]
+    pub fn at_column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// Points the error at the start of `text`, if it is a slice of `line`.
+    #[must_use]
+    pub fn at(mut self, line: &str, text: &str) -> Self {
+        self.column = column_of(line, text);
+        self
+    }
+}
+
+impl Display for LineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            LineErrorKind::MissingDelimiter(delimiter) => {
+                write!(f, "expected the delimiter `{delimiter}`")
+            }
+            LineErrorKind::InvalidValue { found, .. } => write!(f, "could not parse `{found}`"),
+            LineErrorKind::InvalidDigit(found) => write!(f, "expected a digit, found `{found}`"),
+            LineErrorKind::Count { expected, found } => {
+                write!(f, "expected {expected} values, found {found}")
+            }
+            LineErrorKind::TooFew { min, found } => {
+                write!(f, "expected at least {min} values, found {found}")
+            }
+        }
+    }
+}
+
+impl Error for LineError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            LineErrorKind::InvalidValue { source, .. } => Some(&**source),
+            _ => None,
+        }
+    }
+}
+
+/// The column (in characters, starting at 1) of the start of `text` within `line`.
+/// Returns [`None`] if `text` is not a slice of `line`.
+pub fn column_of(line: &str, text: &str) -> Option<usize> {
+    let offset = (text.as_ptr() as usize).checked_sub(line.as_ptr() as usize)?;
+    if offset + text.len() > line.len() {
+        return None;
+    }
+    Some(line.get(..offset)?.chars().count() + 1)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Splits the line at the first occurrence of `delimiter`.
+pub fn split_once<'a>(line: &'a str, delimiter: &str) -> Result<(&'a str, &'a str), LineError> {
+    line.split_once(delimiter).ok_or_else(|| {
+        LineError::new(LineErrorKind::MissingDelimiter(delimiter.into()))
+            .at_column(line.chars().count() + 1)
+    })
+}
+
+/// Parses `text`, a slice of `line`, ignoring surrounding whitespace. Errors point at the start of `text`.
+pub fn field<T>(line: &str, text: &str) -> Result<T, LineError>
+where
+    T: FromStr,
+    T::Err: Into<Box<dyn Error + Send + Sync>>,
+{
+    let text = text.trim();
+    text.parse().map_err(|e: T::Err| {
+        LineError::new(LineErrorKind::InvalidValue {
+            found: text.into(),
+            source: e.into(),
+        })
+        .at(line, text)
+    })
+}
+
+/// Extracts all integers of the line, e.g. `[1, -2, 30]` from `x=1, y=-2: 30`.
+///
+/// A `-` is part of the integer if it does not follow a letter or digit, so `1-3` are the integers `1` and `3`.
+pub fn integers<T>(line: &str) -> Result<Vec<T>, LineError>
+where
+    T: FromStr,
+    T::Err: Into<Box<dyn Error + Send + Sync>>,
+{
+    let bytes = line.as_bytes();
+    let mut integers = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let mut start = i;
+        if start > 0
+            && bytes[start - 1] == b'-'
+            && (start < 2 || !bytes[start - 2].is_ascii_alphanumeric())
+        {
+            start -= 1;
+        }
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        integers.push(field(line, &line[start..i])?);
+    }
+
+    Ok(integers)
+}
+
+/// Parses every character of the line as a decimal digit.
+pub fn digits(line: &str) -> Result<Vec<u8>, LineError> {
+    line.chars()
+        .enumerate()
+        .map(|(i, c)| {
+            c.to_digit(10)
+                .and_then(|digit| u8::try_from(digit).ok())
+                .ok_or_else(|| LineError::new(LineErrorKind::InvalidDigit(c)).at_column(i + 1))
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A part of the input with the number of its first line, see [`sections`].
+///
+/// Input level helpers accept a section in place of the whole input and keep the line numbers of errors
+/// relative to the whole input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section<'a> {
+    pub text: &'a str,
+    /// The number of the first line, starting at 1.
+    pub first_line: usize,
+}
+
+impl<'a> Section<'a> {
+    /// Iterates over the lines of the section with their numbers, starting at [`Section::first_line`].
+    pub fn lines(self) -> impl Iterator<Item = (usize, &'a str)> {
+        (self.first_line..).zip(self.text.lines())
+    }
+}
+
+impl<'a> From<&'a str> for Section<'a> {
+    fn from(text: &'a str) -> Self {
+        Self {
+            text,
+            first_line: 1,
+        }
+    }
+}
+
+/// Splits the input into sections separated by one or more blank lines.
+pub fn sections(input: &str) -> impl Iterator<Item = Section<'_>> {
+    let mut sections = vec![];
+    let mut current: Option<(usize, &str, &str)> = None;
+
+    for (n, line) in Section::from(input).lines() {
+        if line.trim().is_empty() {
+            sections.extend(current.take());
+        } else if let Some((_, _, last)) = &mut current {
+            *last = line;
+        } else {
+            current = Some((n, line, line));
+        }
+    }
+    sections.extend(current);
+
+    sections.into_iter().map(move |(first_line, first, last)| {
+        let start = first.as_ptr() as usize - input.as_ptr() as usize;
+        let end = last.as_ptr() as usize - input.as_ptr() as usize + last.len();
+        Section {
+            text: &input[start..end],
+            first_line,
+        }
+    })
+}
+
+/// Splits the input into exactly `N` sections, see [`sections`].
+pub fn sections_exact<const N: usize>(input: &str) -> Result<[Section<'_>; N], InputError> {
+    let sections: Vec<_> = sections(input).collect();
+    let found = sections.len();
+
+    sections.try_into().map_err(|sections: Vec<Section>| {
+        let count = LineError::new(LineErrorKind::Count { expected: N, found });
+        match sections.get(N) {
+            Some(extra) => InputError::new(extra.first_line, first_line(extra.text), count),
+            None => InputError::new(input.lines().count() + 1, "", count),
+        }
+    })
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or_default()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parses every line of the input (or of a [`Section`]) as `T`.
+pub fn lines<'a, T>(input: impl Into<Section<'a>>) -> Result<Vec<T>, InputError>
+where
+    T: FromStr,
+    T::Err: Into<Box<dyn Error + Send + Sync>>,
+{
+    lines_with(input, str::parse::<T>)
+}
+
+/// Parses every line of the input (or of a [`Section`]) with `f`.
+///
+/// Errors are wrapped in an [`InputError`] of the line. A [`LineError`] also sets the column of the [`InputError`].
+pub fn lines_with<'a, T, E>(
+    input: impl Into<Section<'a>>,
+    mut f: impl FnMut(&'a str) -> Result<T, E>,
+) -> Result<Vec<T>, InputError>
+where
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
+    input
+        .into()
+        .lines()
+        .map(|(n, line)| f(line).map_err(|e| line_error(n, line, e.into())))
+        .collect()
+}
+
+/// Parses every line as digits, see [`digits`]. All lines need to have the same length.
+pub fn digit_grid<'a>(input: impl Into<Section<'a>>) -> Result<Grid<u8>, InputError> {
+    rectangular(input, digits)
+}
+
+/// Splits every line into characters. All lines need to have the same length.
+pub fn char_grid<'a>(input: impl Into<Section<'a>>) -> Result<Grid<char>, InputError> {
+    rectangular(input, |line| Ok(line.chars().collect()))
+}
+
+/// Parses every line into a row of a [`Grid`] with `f`, checking that all rows have the same length.
+pub(crate) fn rectangular<'a, T>(
+    input: impl Into<Section<'a>>,
+    mut f: impl FnMut(&'a str) -> Result<Vec<T>, LineError>,
+) -> Result<Grid<T>, InputError> {
+    let mut width = None;
+
+    let rows = lines_with(input, |line| {
+        let row = f(line)?;
+        let expected = *width.get_or_insert(row.len());
+        if row.len() != expected {
+            let found = row.len();
+            return Err(LineError::new(LineErrorKind::Count { expected, found })
+                .at_column(expected.min(found) + 1));
+        }
+        Ok(row)
+    })?;
+
+    Ok(Grid::from_rows(rows).expect("rows should have the same length"))
+}
+
+fn line_error(n: usize, line: &str, error: Box<dyn Error + Send + Sync>) -> InputError {
+    let column = error
+        .downcast_ref::<LineError>()
+        .and_then(|error| error.column);
+    let error = InputError::new(n, line, error);
+    match column {
+        Some(column) => error.at_column(column),
+        None => error,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_integers() {
+        assert_eq!(integers::<i32>("x=1, y=-2: 30").unwrap(), vec![1, -2, 30]);
+        assert_eq!(integers::<u32>("1-3 a: abc").unwrap(), vec![1, 3]);
+        assert_eq!(integers::<u32>("3   4").unwrap(), vec![3, 4]);
+        assert!(integers::<u32>("none").unwrap().is_empty());
+
+        let error = integers::<u8>("1 256").unwrap_err();
+        assert_eq!(error.column, Some(3));
+        assert_eq!(error.to_string(), "could not parse `256`");
+        assert!(integers::<u8>("-1").is_err());
+    }
+
+    #[test]
+    fn parses_fields() {
+        let line = "3267: 81 40";
+        let (result, numbers) = split_once(line, ":").unwrap();
+        assert_eq!(field::<u32>(line, result).unwrap(), 3267);
+        assert_eq!(numbers, " 81 40");
+
+        assert_eq!(
+            field::<u32>("a: x1", &"a: x1"[2..]).unwrap_err().column,
+            Some(4)
+        );
+        assert_eq!(split_once("abc", "|").unwrap_err().column, Some(4));
+        assert_eq!(column_of("abc", "other"), None);
+    }
+
+    #[test]
+    fn parses_digits() {
+        assert_eq!(digits("2333").unwrap(), vec![2, 3, 3, 3]);
+        let error = digits("23x3").unwrap_err();
+        assert_eq!(error.column, Some(3));
+        assert_eq!(error.to_string(), "expected a digit, found `x`");
+    }
+
+    #[test]
+    fn splits_sections() {
+        let input = "47|53\n97|13\n\n\n75,47\n61,13\n";
+        let sections: Vec<_> = sections(input).collect();
+        assert_eq!(
+            sections,
+            vec![
+                Section {
+                    text: "47|53\n97|13",
+                    first_line: 1
+                },
+                Section {
+                    text: "75,47\n61,13",
+                    first_line: 5
+                },
+            ]
+        );
+
+        assert!(sections_exact::<2>(input).is_ok());
+        assert_eq!(sections_exact::<3>(input).unwrap_err().line, 7);
+        assert_eq!(sections_exact::<1>(input).unwrap_err().line, 5);
+    }
+
+    #[test]
+    fn parses_lines() {
+        assert_eq!(lines::<u32>("1\n2\n").unwrap(), vec![1, 2]);
+
+        let error = lines::<u32>("1\nx\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, None));
+
+        let [_, updates] = sections_exact("1|2\n\n3,4\n5,-6\n").unwrap();
+        let error = lines_with(updates, integers::<u32>).unwrap_err();
+        assert_eq!((error.line, error.column), (4, Some(3)));
+        assert_eq!(error.text, "5,-6");
+    }
+
+    #[test]
+    fn parses_grids() {
+        assert_eq!(digit_grid("12\n34").unwrap()[(1, 0)], 2);
+        assert_eq!(char_grid(".#\n#.").unwrap().row(1), &['#', '.']);
+
+        let error = char_grid("..\n...").unwrap_err();
+        assert_eq!((error.line, error.column), (2, Some(3)));
+    }
+}
@@ -0,0 +1,502 @@
+//! Records the steps of a simulation as frames, to be played back with `cargo replay <day>`.
+//!
+//! Solutions create a [`Recorder`] and push a frame per step. Like visualizations (see [`crate::viz`]), frames are
+//! built lazily and only recorded when the solution runs with `--record`, which `cargo replay` passes:
+//!
+//! ```no_run
+//! use advent_of_code::replay::Recorder;
+//! # use advent_of_code::template::{PuzzleId, Year};
+//! # let puzzle = PuzzleId::new(Year::configured(), advent_of_code::day!(9));
+//!
+//! let mut recorder = Recorder::new(puzzle, "counter");
+//! for i in 0..10 {
+//!     recorder.text(|| format!("step {i}"));
+//! }
+//! ```
+//!
+//! Recordings are stored in `data/<year>/replay/<day>/<name>.replay`. Every frame only lists the cells that changed
+//! since the previous one, so long simulations of large grids stay small.
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::{env, iter};
+
+use itertools::Itertools;
+
+use crate::template::{data_path, PuzzleId};
+use crate::viz::{Cell, Frame, Rgb};
+
+/// The extension of recordings.
+pub const EXTENSION: &str = "replay";
+
+/// Whether this process records, i.e. runs with `--record` and not with `--time`.
+pub fn is_enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| {
+        let args: Vec<String> = env::args().collect();
+        args.iter().any(|arg| arg == "--record") && !args.iter().any(|arg| arg == "--time")
+    })
+}
+
+/// The folder for the recordings of `puzzle`, e.g. `data/2024/replay/09/`.
+pub fn recordings_dir(puzzle: PuzzleId) -> PathBuf {
+    data_path(
+        puzzle.year,
+        Path::new("replay").join(puzzle.day.to_string()),
+    )
+}
+
+/// The recordings of `puzzle`, sorted by name.
+pub fn recordings(puzzle: PuzzleId) -> io::Result<Vec<PathBuf>> {
+    let mut recordings: Vec<PathBuf> = fs::read_dir(recordings_dir(puzzle))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == EXTENSION)
+        })
+        .collect();
+    recordings.sort();
+    Ok(recordings)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Writes the frames of a simulation to a recording.
+#[derive(Debug)]
+pub struct Recorder {
+    writer: Option<BufWriter<File>>,
+    previous: Option<Frame>,
+    frames: usize,
+}
+
+impl Recorder {
+    /// A recorder for the recording `name` of `puzzle`, which replaces an earlier recording of the same name.
+    ///
+    /// Records nothing unless the solution runs with `--record`. Errors are printed and stop the recording.
+    pub fn new(puzzle: PuzzleId, name: &str) -> Self {
+        if !is_enabled() {
+            return Self::disabled();
+        }
+
+        let dir = recordings_dir(puzzle);
+        let path = dir.join(format!("{name}.{EXTENSION}"));
+        let file = fs::create_dir_all(&dir).and_then(|()| File::create(&path));
+
+        match file {
+            Ok(file) => Self {
+                writer: Some(BufWriter::new(file)),
+                previous: None,
+                frames: 0,
+            },
+            Err(e) => {
+                eprintln!("could not record to {}: {e}", path.display());
+                Self::disabled()
+            }
+        }
+    }
+
+    /// A recorder that records nothing.
+    pub fn disabled() -> Self {
+        Self {
+            writer: None,
+            previous: None,
+            frames: 0,
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.writer.is_some()
+    }
+
+    /// The number of recorded frames.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Records the frame built by `frame`.
+    pub fn frame(&mut self, frame: impl FnOnce() -> Frame) {
+        let Some(writer) = &mut self.writer else {
+            return;
+        };
+
+        let frame = frame();
+        if let Err(e) = write_frame(writer, self.previous.as_ref(), &frame) {
+            eprintln!("could not record frame {}: {e}", self.frames);
+            self.writer = None;
+            return;
+        }
+
+        self.previous = Some(frame);
+        self.frames += 1;
+    }
+
+    /// Records the text built by `text` as a frame, e.g. the [`Display`](std::fmt::Display) output of a state.
+    pub fn text(&mut self, text: impl FnOnce() -> String) {
+        self.frame(|| Frame::from_text(&text()));
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        if let Some(writer) = &mut self.writer {
+            let _ = writer.flush();
+        }
+    }
+}
+
+/// Writes a frame as `frame <width> <height> <changes>`, followed by a `<x> <y> <color> <glyph>` line per cell that
+/// changed since `previous`. Every cell changes if the size does.
+fn write_frame(writer: &mut impl Write, previous: Option<&Frame>, frame: &Frame) -> io::Result<()> {
+    let same_size = previous.filter(|previous| {
+        (previous.width(), previous.height()) == (frame.width(), frame.height())
+    });
+
+    let changes: Vec<_> = frame
+        .iter()
+        .filter(|&(position, cell)| {
+            same_size.and_then(|previous| previous.get(position)) != Some(cell)
+        })
+        .collect();
+
+    writeln!(
+        writer,
+        "frame {} {} {}",
+        frame.width(),
+        frame.height(),
+        changes.len()
+    )?;
+    for ((x, y), cell) in changes {
+        let color = cell.color.map_or("-".to_string(), |Rgb(r, g, b)| {
+            format!("{r:02x}{g:02x}{b:02x}")
+        });
+        writeln!(writer, "{x} {y} {color} {}", cell.glyph)?;
+    }
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Reads the frames of a recording.
+pub struct Replay {
+    lines: io::Lines<BufReader<File>>,
+    frame: Option<Frame>,
+}
+
+impl Replay {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            lines: BufReader::new(File::open(path)?).lines(),
+            frame: None,
+        })
+    }
+
+    /// Reads the next frame, [`None`] at the end of the recording.
+    pub fn next_frame(&mut self) -> io::Result<Option<&Frame>> {
+        let Some(header) = self.lines.next().transpose()? else {
+            return Ok(None);
+        };
+
+        let [width, height, changes] = parse_fields(header.strip_prefix("frame "), &header)?;
+        let frame = match self.frame.take() {
+            Some(frame) if (frame.width(), frame.height()) == (width, height) => frame,
+            _ => Frame::blank(width, height),
+        };
+        let frame = self.frame.insert(frame);
+
+        for _ in 0..changes {
+            let line = self
+                .lines
+                .next()
+                .transpose()?
+                .ok_or_else(|| invalid_data("the recording ends within a frame"))?;
+
+            // the glyph comes last, it may be a space
+            let (x, y, color, glyph) = line
+                .splitn(4, ' ')
+                .collect_tuple()
+                .ok_or_else(|| invalid_line(&line))?;
+            let [x, y] = [x, y].map(|c| c.parse().map_err(|_| invalid_line(&line)));
+            let color = match color {
+                "-" => None,
+                hex => Some(parse_color(hex).ok_or_else(|| invalid_line(&line))?),
+            };
+            let glyph = glyph.chars().next().ok_or_else(|| invalid_line(&line))?;
+
+            let cell = frame.get_mut((x?, y?)).ok_or_else(|| invalid_line(&line))?;
+            *cell = Cell::new(glyph, color);
+        }
+
+        Ok(self.frame.as_ref())
+    }
+
+    /// Reads every frame with `f`.
+    pub fn for_each(mut self, mut f: impl FnMut(&Frame)) -> io::Result<()> {
+        while let Some(frame) = self.next_frame()? {
+            f(frame);
+        }
+        Ok(())
+    }
+}
+
+fn parse_fields<const N: usize>(fields: Option<&str>, line: &str) -> io::Result<[usize; N]> {
+    let values: Vec<usize> = fields
+        .ok_or_else(|| invalid_line(line))?
+        .split(' ')
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .map_err(|_| invalid_line(line))?;
+
+    values.try_into().map_err(|_| invalid_line(line))
+}
+
+fn parse_color(hex: &str) -> Option<Rgb> {
+    let value = u32::from_str_radix(hex, 16)
+        .ok()
+        .filter(|_| hex.len() == 6)?;
+    let [_, r, g, b] = value.to_be_bytes();
+    Some(Rgb(r, g, b))
+}
+
+fn invalid_line(line: &str) -> io::Error {
+    invalid_data(&format!("invalid line in recording: {line:?}"))
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Writes frames to an animated GIF, with a square of `scale` pixels per cell.
+///
+/// The size of the animation is the size of the first frame, later frames are cropped or padded to it. Colors are
+/// mapped to a fixed palette of the [`Rgb`] constants and a 6x6x6 color cube.
+pub struct GifWriter<W: Write> {
+    writer: W,
+    scale: usize,
+    size: Option<(usize, usize)>,
+    /// The delay after each frame in hundredths of a second.
+    delay: u16,
+    palette: Vec<Rgb>,
+    indices: HashMap<Rgb, u8>,
+}
+
+/// GIF images are limited to 65535 pixels in either direction.
+const GIF_MAX_SIZE: usize = u16::MAX as usize;
+
+impl<W: Write> GifWriter<W> {
+    pub fn new(writer: W, scale: usize, fps: u32) -> Self {
+        let named = [
+            Rgb::BLACK,
+            Rgb::GRAY,
+            Rgb::WHITE,
+            Rgb::RED,
+            Rgb::GREEN,
+            Rgb::BLUE,
+            Rgb::YELLOW,
+        ];
+        let levels = [0x00, 0x33, 0x66, 0x99, 0xcc, 0xff];
+        let cube = levels.into_iter().flat_map(move |r| {
+            levels
+                .into_iter()
+                .flat_map(move |g| levels.into_iter().map(move |b| Rgb(r, g, b)))
+        });
+        let palette: Vec<Rgb> = named
+            .into_iter()
+            .chain(cube)
+            .chain(iter::repeat(Rgb::BLACK))
+            .take(256)
+            .collect();
+
+        Self {
+            writer,
+            scale: scale.max(1),
+            size: None,
+            delay: u16::try_from(100 / fps.max(1)).unwrap_or(u16::MAX).max(1),
+            palette,
+            indices: HashMap::new(),
+        }
+    }
+
+    /// The palette index of the color closest to `color`.
+    fn index(&mut self, color: Rgb) -> u8 {
+        let palette = &self.palette;
+        *self.indices.entry(color).or_insert_with(|| {
+            let distance = |&Rgb(r, g, b): &Rgb| {
+                let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2);
+                d(r, color.0) + d(g, color.1) + d(b, color.2)
+            };
+            let (index, _) = palette
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, candidate)| distance(candidate))
+                .expect("the palette is not empty");
+            index as u8
+        })
+    }
+
+    /// Writes the header before the first frame.
+    fn start(&mut self, frame: &Frame) -> io::Result<(usize, usize)> {
+        let max_cells = GIF_MAX_SIZE / self.scale;
+        let size = (
+            frame.width().clamp(1, max_cells),
+            frame.height().clamp(1, max_cells),
+        );
+        let (width, height) = (size.0 * self.scale, size.1 * self.scale);
+
+        self.writer.write_all(b"GIF89a")?;
+        self.writer.write_all(&(width as u16).to_le_bytes())?;
+        self.writer.write_all(&(height as u16).to_le_bytes())?;
+        // global color table of 256 colors, no background color or aspect ratio
+        self.writer.write_all(&[0xf7, 0, 0])?;
+        for &Rgb(r, g, b) in &self.palette {
+            self.writer.write_all(&[r, g, b])?;
+        }
+        // loop forever
+        self.writer.write_all(&[0x21, 0xff, 0x0b])?;
+        self.writer.write_all(b"NETSCAPE2.0")?;
+        self.writer.write_all(&[0x03, 0x01, 0x00, 0x00, 0x00])?;
+
+        self.size = Some(size);
+        Ok(size)
+    }
+
+    pub fn frame(&mut self, frame: &Frame) -> io::Result<()> {
+        let (width, height) = match self.size {
+            Some(size) => size,
+            None => self.start(frame)?,
+        };
+
+        let scale = self.scale;
+        let mut pixels = Vec::with_capacity(width * height * scale * scale);
+        for y in 0..height {
+            let row: Vec<u8> = (0..width)
+                .map(|x| {
+                    let color = frame.get((x, y)).map_or(Rgb::BLACK, Cell::rgb);
+                    self.index(color)
+                })
+                .flat_map(|index| iter::repeat_n(index, scale))
+                .collect();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&row);
+            }
+        }
+
+        let (width, height) = ((width * self.scale) as u16, (height * self.scale) as u16);
+        // graphic control extension with the delay
+        self.writer.write_all(&[0x21, 0xf9, 0x04, 0x00])?;
+        self.writer.write_all(&self.delay.to_le_bytes())?;
+        self.writer.write_all(&[0x00, 0x00])?;
+        // image descriptor for the whole screen, without a local color table
+        self.writer.write_all(&[0x2c, 0, 0, 0, 0])?;
+        self.writer.write_all(&width.to_le_bytes())?;
+        self.writer.write_all(&height.to_le_bytes())?;
+        self.writer.write_all(&[0x00])?;
+
+        self.writer.write_all(&[8])?;
+        for block in lzw_uncompressed(&pixels).chunks(255) {
+            self.writer.write_all(&[block.len() as u8])?;
+            self.writer.write_all(block)?;
+        }
+        self.writer.write_all(&[0x00])
+    }
+
+    /// Writes the trailer and returns the writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer.write_all(&[0x3b])?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Encodes 8-bit pixels as LZW codes without compressing them, which keeps the encoder trivial.
+///
+/// Every pixel is written as a literal 9-bit code. The decoder still adds a code to its table for each of them, so the
+/// table is cleared before it would need 10-bit codes.
+fn lzw_uncompressed(pixels: &[u8]) -> Vec<u8> {
+    const CLEAR: u16 = 256;
+    const END: u16 = 257;
+    const LITERALS_PER_CLEAR: usize = 250;
+
+    let mut bytes = vec![];
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    let mut write = |code: u16| {
+        buffer |= u32::from(code) << bits;
+        bits += 9;
+        while bits >= 8 {
+            bytes.push(buffer as u8);
+            buffer >>= 8;
+            bits -= 8;
+        }
+    };
+
+    for chunk in pixels.chunks(LITERALS_PER_CLEAR) {
+        write(CLEAR);
+        for &pixel in chunk {
+            write(u16::from(pixel));
+        }
+    }
+    write(END);
+
+    if bits > 0 {
+        bytes.push(buffer as u8);
+    }
+    bytes
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use super::{write_frame, GifWriter, Replay};
+    use crate::viz::{Frame, Rgb, Style};
+
+    #[test]
+    fn replays_recorded_frames() {
+        let first = Frame::from_text("ab\ncd");
+        let second = first.clone().overlay([(1, 1)], Style::new(' ', Rgb::RED));
+        let third = Frame::from_text("xyz");
+
+        let mut recording = vec![];
+        write_frame(&mut recording, None, &first).unwrap();
+        write_frame(&mut recording, Some(&first), &second).unwrap();
+        write_frame(&mut recording, Some(&second), &third).unwrap();
+
+        let text = String::from_utf8(recording.clone()).unwrap();
+        assert!(text.contains("frame 2 2 1\n1 1 e04040  \n"));
+
+        let path = env::temp_dir().join(format!("replay-test-{}.replay", std::process::id()));
+        fs::write(&path, recording).unwrap();
+
+        let mut frames = vec![];
+        Replay::open(&path)
+            .unwrap()
+            .for_each(|frame| frames.push(frame.to_ansi()))
+            .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(frames, [first.to_ansi(), second.to_ansi(), third.to_ansi()]);
+    }
+
+    #[test]
+    fn writes_gifs() {
+        let mut gif = GifWriter::new(vec![], 2, 10);
+        gif.frame(&Frame::from_text("#.\n.#")).unwrap();
+        gif.frame(&Frame::from_text("#")).unwrap();
+        let bytes = gif.finish().unwrap();
+
+        assert_eq!(&bytes[..6], b"GIF89a");
+        // 4x4 pixels
+        assert_eq!(bytes[6..10], [4, 0, 4, 0]);
+        // after the palette and the loop extension, the first frame waits 10/100s
+        assert_eq!(bytes[800..808], [0x21, 0xf9, 0x04, 0x00, 10, 0, 0x00, 0x00]);
+        assert_eq!(bytes[808], 0x2c);
+        assert_eq!(bytes.last(), Some(&0x3b));
+    }
+}
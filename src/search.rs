@@ -0,0 +1,657 @@
+//! Graph searches over neighbour functions: breadth-first, depth-first, Dijkstra and A*.
+//!
+//! Graphs are never built explicitly. Instead every search takes the start nodes and a function that returns the
+//! successors of a node, so the nodes can be grid positions, puzzle states or anything else that is hashable:
+//!
+//! ```
+//! use advent_of_code::grid::Grid;
+//! use advent_of_code::search;
+//!
+//! let maze: Grid<char> = "S.#\n#.#\n..E".parse().unwrap();
+//! let start = maze.find(|&c| c == 'S').unwrap();
+//! let walk = search::grid4(&maze, |_, &to| to != '#');
+//!
+//! let (end, paths) = search::bfs_to([start], walk, |&p| maze[p] == 'E').unwrap();
+//! assert_eq!(paths.cost(&end), Some(4));
+//! assert_eq!(paths.path_to(&end).unwrap(), [(0, 0), (1, 0), (1, 1), (1, 2), (2, 2)]);
+//! ```
+use std::array;
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::iter;
+use std::ops::Add;
+
+use crate::grid::{Grid, Offset, Position, NEIGHBOURS_4, NEIGHBOURS_8};
+
+/// The cost of an edge or path, e.g. an integer. [`Default::default`] is the cost of an empty path.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<C: Copy + Ord + Add<Output = C> + Default> Cost for C {}
+
+/// The goal found by a search together with every shortest path to it.
+pub type Goal<N, C> = (N, Paths<N, C>);
+
+/// A node reached by a search.
+#[derive(Debug, Clone)]
+struct Visit<N, C> {
+    cost: C,
+    /// The nodes before this one on any of its shortest paths, empty for start nodes.
+    predecessors: Vec<N>,
+    /// Whether the successors of the node were expanded with its current cost.
+    closed: bool,
+}
+
+/// The nodes reached by a search, with the costs of and predecessors on their shortest paths.
+#[derive(Debug, Clone)]
+pub struct Paths<N, C> {
+    visits: HashMap<N, Visit<N, C>>,
+}
+
+impl<N: Clone + Eq + Hash, C: Cost> Paths<N, C> {
+    fn new() -> Self {
+        Self {
+            visits: HashMap::new(),
+        }
+    }
+
+    /// Records a start node, returns `false` if it was recorded before.
+    fn start(&mut self, node: N) -> bool {
+        match self.visits.entry(node) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(entry) => {
+                entry.insert(Visit {
+                    cost: C::default(),
+                    predecessors: vec![],
+                    closed: false,
+                });
+                true
+            }
+        }
+    }
+
+    /// Records that `next` is reached from `node` with a path of `cost`, returns it if this improves its cost.
+    fn relax(&mut self, node: &N, next: N, cost: C) -> Option<N> {
+        match self.visits.entry(next) {
+            Entry::Occupied(mut entry) => {
+                let visit = entry.get_mut();
+                match cost.cmp(&visit.cost) {
+                    Ordering::Less => {
+                        *visit = Visit {
+                            cost,
+                            predecessors: vec![node.clone()],
+                            closed: false,
+                        };
+                        Some(entry.key().clone())
+                    }
+                    Ordering::Equal => {
+                        visit.predecessors.push(node.clone());
+                        None
+                    }
+                    Ordering::Greater => None,
+                }
+            }
+            Entry::Vacant(entry) => {
+                let next = entry.key().clone();
+                entry.insert(Visit {
+                    cost,
+                    predecessors: vec![node.clone()],
+                    closed: false,
+                });
+                Some(next)
+            }
+        }
+    }
+
+    /// Whether `node` was reached.
+    pub fn contains(&self, node: &N) -> bool {
+        self.visits.contains_key(node)
+    }
+
+    /// The number of reached nodes.
+    pub fn len(&self) -> usize {
+        self.visits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.visits.is_empty()
+    }
+
+    /// The cost of the shortest path to `node`, [`None`] if it was not reached.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.visits.get(node).map(|visit| visit.cost)
+    }
+
+    /// The reached nodes with the costs of their shortest paths, in arbitrary order.
+    pub fn costs(&self) -> impl Iterator<Item = (&N, C)> {
+        self.visits.iter().map(|(node, visit)| (node, visit.cost))
+    }
+
+    /// The nodes before `node` on any of its shortest paths.
+    pub fn predecessors(&self, node: &N) -> &[N] {
+        self.visits
+            .get(node)
+            .map_or(&[], |visit| visit.predecessors.as_slice())
+    }
+
+    /// One of the shortest paths to `node`, from a start node to `node` itself.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.visits.get(node)?;
+
+        let mut path: Vec<N> = iter::successors(Some(node.clone()), |node| {
+            self.predecessors(node).first().cloned()
+        })
+        .collect();
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every shortest path to `node`, from a start node to `node` itself.
+    ///
+    /// The number of paths can grow exponentially with their length, prefer [`Paths::nodes_on_paths_to`] if only the
+    /// nodes are needed.
+    pub fn all_paths_to(&self, node: &N) -> Vec<Vec<N>> {
+        if !self.contains(node) {
+            return vec![];
+        }
+
+        let predecessors = self.predecessors(node);
+        if predecessors.is_empty() {
+            return vec![vec![node.clone()]];
+        }
+
+        predecessors
+            .iter()
+            .flat_map(|predecessor| self.all_paths_to(predecessor))
+            .map(|mut path| {
+                path.push(node.clone());
+                path
+            })
+            .collect()
+    }
+
+    /// The nodes on any of the shortest paths to `node`, including the start nodes and `node` itself.
+    pub fn nodes_on_paths_to(&self, node: &N) -> HashSet<N> {
+        let mut nodes = HashSet::new();
+        if !self.contains(node) {
+            return nodes;
+        }
+
+        let mut stack = vec![node.clone()];
+        while let Some(node) = stack.pop() {
+            stack.extend(self.predecessors(&node).iter().cloned());
+            nodes.insert(node);
+        }
+        nodes
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Searches every node reachable from `starts` breadth-first, i.e. the cost of a path is its number of edges.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+) -> Paths<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    breadth_first(starts, successors, |_| false).0
+}
+
+/// Searches breadth-first from `starts` until a node satisfies `goal`, returns that node and every shortest path to
+/// it.
+pub fn bfs_to<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<Goal<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let (paths, goal) = breadth_first(starts, successors, goal);
+    goal.map(|goal| (goal, paths))
+}
+
+fn breadth_first<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> (Paths<N, usize>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = Paths::new();
+    let mut queue: VecDeque<N> = starts
+        .into_iter()
+        .filter(|start| paths.start(start.clone()))
+        .collect();
+
+    while let Some(node) = queue.pop_front() {
+        // the predecessors of the goal are one level above it, so they are all expanded by now
+        if goal(&node) {
+            return (paths, Some(node));
+        }
+
+        let cost = paths.visits[&node].cost + 1;
+        for next in successors(&node) {
+            queue.extend(paths.relax(&node, next, cost));
+        }
+    }
+
+    (paths, None)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Searches every node reachable from `starts` with Dijkstra's algorithm, `successors` returns the nodes after a node
+/// together with the costs of the edges to them.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+) -> Paths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    best_first(starts, successors, |_| C::default(), |_| false).0
+}
+
+/// Searches with Dijkstra's algorithm from `starts` until a node satisfies `goal`, returns that node and every
+/// shortest path to it.
+pub fn dijkstra_to<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<Goal<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (paths, goal) = best_first(starts, successors, |_| C::default(), goal);
+    goal.map(|goal| (goal, paths))
+}
+
+/// Searches with A* from `starts` until a node satisfies `goal`, returns that node and every shortest path to it.
+///
+/// The `heuristic` estimates the cost from a node to the nearest goal. The paths are only guaranteed to be shortest if
+/// it never overestimates, e.g. the Manhattan distance on a grid.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<Goal<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (paths, goal) = best_first(starts, successors, heuristic, goal);
+    goal.map(|goal| (goal, paths))
+}
+
+/// A node in the queue of [`best_first`], ordered by its estimated total cost, lowest first.
+struct Queued<N, C> {
+    estimate: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimate == other.estimate
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.cmp(&self.estimate)
+    }
+}
+
+fn best_first<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> (Paths<N, C>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        if paths.start(start.clone()) {
+            queue.push(Queued {
+                estimate: heuristic(&start),
+                cost: C::default(),
+                node: start,
+            });
+        }
+    }
+
+    let mut found: Option<(N, C)> = None;
+    while let Some(Queued {
+        estimate,
+        cost,
+        node,
+    }) = queue.pop()
+    {
+        // keep expanding nodes as cheap as the goal, they may be predecessors on other shortest paths
+        if found.as_ref().is_some_and(|&(_, limit)| estimate > limit) {
+            break;
+        }
+
+        let visit = paths
+            .visits
+            .get_mut(&node)
+            .expect("queued nodes are visited");
+        if visit.closed || cost > visit.cost {
+            continue;
+        }
+        visit.closed = true;
+
+        if found.is_none() && goal(&node) {
+            found = Some((node, estimate));
+            continue;
+        }
+
+        for (next, step) in successors(&node) {
+            let cost = cost + step;
+            if let Some(next) = paths.relax(&node, next, cost) {
+                queue.push(Queued {
+                    estimate: cost + heuristic(&next),
+                    cost,
+                    node: next,
+                });
+            }
+        }
+    }
+
+    (paths, found.map(|(goal, _)| goal))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Iterates over every node reachable from `start` in depth-first order, starting with `start` itself.
+pub fn dfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> impl Iterator<Item = N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = HashSet::new();
+    let mut stack = vec![start];
+
+    iter::from_fn(move || loop {
+        let node = stack.pop()?;
+        if visited.insert(node.clone()) {
+            // reversed, so the first successor is visited first
+            let next: Vec<N> = successors(&node).into_iter().collect();
+            stack.extend(
+                next.into_iter()
+                    .rev()
+                    .filter(|next| !visited.contains(next)),
+            );
+            return Some(node);
+        }
+    })
+}
+
+/// Searches depth-first from `start` until a node satisfies `goal`, returns a path from `start` to it.
+///
+/// Unlike [`bfs_to`] the path is not necessarily the shortest one, but only the current path is kept on the stack.
+pub fn dfs_path<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = HashSet::from([start.clone()]);
+    let mut path = vec![start];
+    let mut pending = vec![successors(&path[0]).into_iter()];
+
+    while let Some(next) = pending.last_mut() {
+        if goal(
+            path.last()
+                .expect("the path is as long as the pending successors"),
+        ) {
+            return Some(path);
+        }
+
+        match next.find(|node| !visited.contains(node)) {
+            Some(node) => {
+                visited.insert(node.clone());
+                pending.push(successors(&node).into_iter());
+                path.push(node);
+            }
+            None => {
+                pending.pop();
+                path.pop();
+            }
+        }
+    }
+
+    None
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Successors of a grid position in the 4 directions, see [`grid4`].
+pub type GridSuccessors<const N: usize> = iter::Flatten<array::IntoIter<Option<Position>, N>>;
+
+/// A neighbour function that walks `grid` horizontally and vertically.
+///
+/// A step is only taken if `passable(from, to)` holds for the cells it leaves and enters, e.g. `|_, &to| to != '#'`
+/// for walls or `|&from, &to| to <= from + 1` for height maps.
+pub fn grid4<'a, T>(
+    grid: &'a Grid<T>,
+    passable: impl FnMut(&T, &T) -> bool + 'a,
+) -> impl FnMut(&Position) -> GridSuccessors<4> + 'a {
+    grid_successors(grid, NEIGHBOURS_4, passable)
+}
+
+/// A neighbour function that walks `grid` in all 8 directions, see [`grid4`].
+pub fn grid8<'a, T>(
+    grid: &'a Grid<T>,
+    passable: impl FnMut(&T, &T) -> bool + 'a,
+) -> impl FnMut(&Position) -> GridSuccessors<8> + 'a {
+    grid_successors(grid, NEIGHBOURS_8, passable)
+}
+
+fn grid_successors<'a, T, const N: usize>(
+    grid: &'a Grid<T>,
+    offsets: [Offset; N],
+    mut passable: impl FnMut(&T, &T) -> bool + 'a,
+) -> impl FnMut(&Position) -> GridSuccessors<N> + 'a {
+    move |&position| {
+        offsets
+            .map(|offset| {
+                grid.offset(position, offset)
+                    .filter(|&next| passable(&grid[position], &grid[next]))
+            })
+            .into_iter()
+            .flatten()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A graph of puzzle states that is explored on demand, e.g. positions together with a direction or the keys
+/// collected so far.
+///
+/// Implementing the trait bundles the neighbour function, the goal and an optional heuristic, so the shortest path is
+/// found with [`StateGraph::shortest_paths`].
+pub trait StateGraph {
+    type State: Clone + Eq + Hash;
+    type Cost: Cost;
+
+    /// The states after `state`, together with the costs of getting there.
+    fn successors<'a>(
+        &'a self,
+        state: &Self::State,
+    ) -> impl IntoIterator<Item = (Self::State, Self::Cost)> + use<'a, Self>;
+
+    fn is_goal(&self, state: &Self::State) -> bool;
+
+    /// Estimates the cost from `state` to the nearest goal, see [`astar`]. Defaults to zero, i.e. Dijkstra's algorithm.
+    fn heuristic(&self, _state: &Self::State) -> Self::Cost {
+        Self::Cost::default()
+    }
+
+    /// Finds the nearest goal from `starts` and every shortest path to it.
+    fn shortest_paths(
+        &self,
+        starts: impl IntoIterator<Item = Self::State>,
+    ) -> Option<Goal<Self::State, Self::Cost>>
+    where
+        Self: Sized,
+    {
+        astar(
+            starts,
+            |state| self.successors(state),
+            |state| self.heuristic(state),
+            |state| self.is_goal(state),
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{astar, bfs, bfs_to, dfs, dfs_path, dijkstra, dijkstra_to, grid4, StateGraph};
+    use crate::geometry::{self, Direction};
+    use crate::grid::{Grid, Position};
+
+    const MAZE: &str = "\
+        S...#\n\
+        .##.#\n\
+        ....E";
+
+    fn maze() -> (Grid<char>, Position, Position) {
+        let maze: Grid<char> = MAZE.parse().unwrap();
+        let start = maze.find(|&c| c == 'S').unwrap();
+        let end = maze.find(|&c| c == 'E').unwrap();
+        (maze, start, end)
+    }
+
+    #[test]
+    fn searches_breadth_first() {
+        let (maze, start, end) = maze();
+
+        let paths = bfs([start], grid4(&maze, |_, &to| to != '#'));
+        assert_eq!(paths.len(), 11);
+        assert_eq!(paths.cost(&end), Some(6));
+        assert_eq!(paths.cost(&(4, 0)), None);
+
+        let (goal, paths) =
+            bfs_to([start], grid4(&maze, |_, &to| to != '#'), |&p| p == end).unwrap();
+        assert_eq!(goal, end);
+        assert_eq!(paths.path_to(&end).unwrap().len(), 7);
+        assert_eq!(paths.all_paths_to(&end).len(), 2);
+        assert_eq!(paths.nodes_on_paths_to(&end).len(), 11);
+        assert!(bfs_to([start], grid4(&maze, |_, _| false), |&p| p == end).is_none());
+    }
+
+    #[test]
+    fn searches_weighted_graphs() {
+        // 0 -1-> 1 -1-> 3, 0 -2-> 2 -0-> 3, 3 -5-> 4
+        let edges = |&node: &u32| match node {
+            0 => vec![(1, 1), (2, 2)],
+            1 => vec![(3, 1)],
+            2 => vec![(3, 0)],
+            3 => vec![(4, 5)],
+            _ => vec![],
+        };
+
+        let paths = dijkstra([0], edges);
+        assert_eq!(paths.cost(&3), Some(2));
+        assert_eq!(paths.cost(&4), Some(7));
+        assert_eq!(paths.all_paths_to(&4), [vec![0, 1, 3, 4], vec![0, 2, 3, 4]]);
+
+        let (goal, paths) = dijkstra_to([0], edges, |&node| node >= 3).unwrap();
+        assert_eq!(goal, 3);
+        assert_eq!(paths.predecessors(&3).len(), 2);
+        assert_eq!(paths.cost(&4), None);
+
+        let (_, paths) = astar([0], edges, |&node| 4 - node.min(4), |&node| node == 4).unwrap();
+        assert_eq!(paths.cost(&4), Some(7));
+    }
+
+    #[test]
+    fn searches_depth_first() {
+        let (maze, start, end) = maze();
+
+        let order: Vec<_> = dfs(start, grid4(&maze, |_, &to| to != '#')).collect();
+        assert_eq!(order.len(), 11);
+        assert_eq!(order[..3], [(0, 0), (1, 0), (2, 0)]);
+
+        let path = dfs_path(start, grid4(&maze, |_, &to| to != '#'), |&p| p == end).unwrap();
+        assert_eq!((path[0], path[path.len() - 1]), (start, end));
+        assert!(path
+            .windows(2)
+            .all(|w| geometry::manhattan(w[0], w[1]) == 1));
+    }
+
+    /// Walking the maze costs 1 per step and 10 per turn.
+    struct Reindeer(Grid<char>);
+
+    impl StateGraph for Reindeer {
+        type State = (Position, Direction);
+        type Cost = u32;
+
+        fn successors<'a>(
+            &'a self,
+            &(position, direction): &Self::State,
+        ) -> impl IntoIterator<Item = (Self::State, u32)> + use<'a> {
+            let forward = self
+                .0
+                .offset(position, direction)
+                .filter(|&next| self.0[next] != '#')
+                .map(|next| ((next, direction), 1));
+            [
+                ((position, direction.turn_left()), 10),
+                ((position, direction.turn_right()), 10),
+            ]
+            .into_iter()
+            .chain(forward)
+        }
+
+        fn is_goal(&self, &(position, _): &Self::State) -> bool {
+            self.0[position] == 'E'
+        }
+    }
+
+    #[test]
+    fn searches_state_graphs() {
+        let (maze, start, end) = maze();
+
+        let (goal, paths) = Reindeer(maze)
+            .shortest_paths([(start, Direction::East)])
+            .unwrap();
+        assert_eq!(goal.0, end);
+        // both ways around the wall take 6 steps and 2 turns
+        assert_eq!(paths.cost(&goal), Some(26));
+        assert_eq!(paths.all_paths_to(&goal).len(), 2);
+    }
+}
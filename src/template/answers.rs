@@ -0,0 +1,243 @@
+/// Accepted answers that solutions are verified against.
+use std::{collections::HashMap, fmt::Display, fs, io::Error, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::{data_path, Day, Part, Year};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// The accepted answers of a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// The accepted answers of every day.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate the answers of a year to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let path = data_path(year, ANSWERS_FILE_NAME);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the answers of a year from a JSON file. If not present, returns empty answers.
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(data_path(year, ANSWERS_FILE_NAME))
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from)
+            .unwrap_or_default()
+    }
+
+    /// The accepted answer of a part, if known.
+    pub fn get(&self, day: Day, part: Part) -> Option<&str> {
+        let answer = self.data.iter().find(|a| a.day == day)?;
+        match part {
+            Part::One => answer.part_1.as_deref(),
+            Part::Two => answer.part_2.as_deref(),
+        }
+    }
+
+    /// Sets the accepted answer of a part, replacing a previous one.
+    pub fn set(&mut self, day: Day, part: Part, answer: String) {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        match part {
+            Part::One => self.data[index].part_1 = Some(answer),
+            Part::Two => self.data[index].part_2 = Some(answer),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The result of verifying an answer against the accepted one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    Correct,
+    Wrong {
+        expected: String,
+    },
+    /// There is no accepted answer for this part yet.
+    Unknown,
+}
+
+impl Check {
+    pub fn new(answer: &str, expected: Option<&str>) -> Self {
+        match expected {
+            None => Self::Unknown,
+            Some(expected) if expected.trim() == answer.trim() => Self::Correct,
+            Some(expected) => Self::Wrong {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => f.write_str("✓"),
+            Self::Wrong { expected } if expected.contains('\n') => f.write_str("✗"),
+            Self::Wrong { expected } => write!(f, "✗ (expected {expected})"),
+            Self::Unknown => f.write_str("?"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        let answer = |x: &Option<String>| x.clone().map_or(JsonValue::Null, JsonValue::String);
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part_1".into(), answer(&value.part_1));
+        map.insert("part_2".into(), answer(&value.part_2));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_2 to be null or string.")?;
+
+        Ok(Answer {
+            day,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{Answers, Check};
+    use crate::{day, template::Part};
+
+    #[test]
+    fn sets_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(9), Part::Two, "2858".into());
+        answers.set(day!(1), Part::One, "11".into());
+        answers.set(day!(9), Part::One, "1928".into());
+        answers.set(day!(9), Part::One, "1929".into());
+
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(1));
+        assert_eq!(answers.get(day!(9), Part::One), Some("1929"));
+        assert_eq!(answers.get(day!(9), Part::Two), Some("2858"));
+        assert_eq!(answers.get(day!(1), Part::Two), None);
+        assert_eq!(answers.get(day!(5), Part::One), None);
+    }
+
+    #[test]
+    fn checks_answers() {
+        assert_eq!(Check::new("42", Some("42")), Check::Correct);
+        assert_eq!(Check::new("42\n", Some("42")), Check::Correct);
+        assert_eq!(
+            Check::new("41", Some("42")),
+            Check::Wrong {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(Check::new("42", None), Check::Unknown);
+        assert_eq!(Check::new("41", Some("42")).to_string(), "✗ (expected 42)");
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), Part::One, "11".into());
+        answers.set(day!(2), Part::Two, "#.\n.#".into());
+
+        let json = JsonValue::from(&answers).stringify().unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+}
@@ -0,0 +1,484 @@
+/// Built-in client for the adventofcode.com website.
+use std::{env, fmt::Display, fs, io, path::PathBuf, str::FromStr, time::Duration};
+
+use crate::template::{markdown, Part, PuzzleId};
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+static USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+/// Selects how the template talks to adventofcode.com.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// The built-in [`Client`].
+    #[default]
+    Native,
+    /// The external `aoc` executable, see [`aoc_cli`](crate::template::aoc_cli).
+    AocCli,
+}
+
+impl Backend {
+    /// Reads the `AOC_BACKEND` environment variable, either `native` (default) or `aoc-cli`.
+    pub fn from_env() -> Self {
+        match env::var("AOC_BACKEND").as_deref() {
+            Ok("aoc-cli") => Self::AocCli,
+            _ => Self::Native,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    MissingSession,
+    BadStatus(u16),
+    Transport(String),
+    IO(io::Error),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or create the file `~/.adventofcode.session`."
+            ),
+            ClientError::BadStatus(404) => write!(
+                f,
+                "the server responded with 404, the puzzle might not be unlocked yet."
+            ),
+            ClientError::BadStatus(400) => write!(
+                f,
+                "the server responded with 400, the session cookie might be expired."
+            ),
+            ClientError::BadStatus(status) => {
+                write!(f, "the server responded with status {status}.")
+            }
+            ClientError::Transport(e) => write!(f, "could not reach the server: {e}"),
+            ClientError::IO(e) => write!(f, "could not read the response: {e}"),
+        }
+    }
+}
+
+impl From<ureq::Error> for ClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, _) => ClientError::BadStatus(status),
+            ureq::Error::Transport(e) => ClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+impl From<io::Error> for ClientError {
+    fn from(e: io::Error) -> Self {
+        ClientError::IO(e)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// Value of the `session` cookie of a logged in browser.
+    pub session: String,
+    /// Defaults to `https://adventofcode.com`, can be pointed to a mock server.
+    pub base_url: String,
+}
+
+impl Config {
+    /// Reads the configuration from the environment:
+    ///  1. the session cookie from `AOC_SESSION`, falling back to the file `~/.adventofcode.session`
+    ///     or `~/.config/adventofcode.session`.
+    ///  2. the base url from `AOC_BASE_URL`.
+    pub fn from_env() -> Result<Self, ClientError> {
+        let session = env::var("AOC_SESSION")
+            .ok()
+            .or_else(read_session_file)
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .ok_or(ClientError::MissingSession)?;
+
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+
+        Ok(Self {
+            session,
+            base_url: base_url.trim_end_matches('/').into(),
+        })
+    }
+}
+
+fn read_session_file() -> Option<String> {
+    let home = env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)?;
+    let config_dir =
+        env::var_os("XDG_CONFIG_HOME").map_or_else(|| home.join(".config"), PathBuf::from);
+
+    [
+        home.join(".adventofcode.session"),
+        config_dir.join("adventofcode.session"),
+    ]
+    .iter()
+    .find_map(|path| fs::read_to_string(path).ok())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The verdict of the server on a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// The answer is wrong, without a hint in which direction.
+    Wrong,
+    /// An answer was submitted too recently.
+    RateLimited,
+    /// The part is already solved or not unlocked yet.
+    WrongLevel,
+    /// The response could not be understood.
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => f.write_str("correct"),
+            Self::TooHigh => f.write_str("too high"),
+            Self::TooLow => f.write_str("too low"),
+            Self::Wrong => f.write_str("wrong"),
+            Self::RateLimited => f.write_str("rate limited"),
+            Self::WrongLevel => f.write_str("wrong level"),
+            Self::Unknown => f.write_str("unknown"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Self::Correct),
+            "too high" => Ok(Self::TooHigh),
+            "too low" => Ok(Self::TooLow),
+            "wrong" => Ok(Self::Wrong),
+            "rate limited" => Ok(Self::RateLimited),
+            "wrong level" => Ok(Self::WrongLevel),
+            "unknown" => Ok(Self::Unknown),
+            _ => Err(format!("unknown verdict `{s}`")),
+        }
+    }
+}
+
+/// The response to a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub verdict: Verdict,
+    /// The time to wait before submitting another answer, if the server reported one.
+    pub wait: Option<Duration>,
+    /// The message of the server, converted to Markdown.
+    pub message: String,
+}
+
+impl Submission {
+    fn from_html(html: &str) -> Self {
+        let message = markdown::articles(html)
+            .next()
+            .map(markdown::to_markdown)
+            .unwrap_or_default();
+
+        let verdict = if message.contains("That's the right answer") {
+            Verdict::Correct
+        } else if message.contains("That's not the right answer") {
+            if message.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if message.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else if message.contains("You gave an answer too recently") {
+            Verdict::RateLimited
+        } else if message.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown
+        };
+
+        Self {
+            verdict,
+            wait: parse_wait(&message),
+            message,
+        }
+    }
+}
+
+/// Reads the cooldown from messages like "You have 1m 5s left to wait." or "Please wait one minute before trying again."
+fn parse_wait(message: &str) -> Option<Duration> {
+    if let Some(start) = message.find("You have ") {
+        let rest = &message[start + "You have ".len()..];
+        let end = rest.find(" left to wait")?;
+
+        return rest[..end]
+            .split_whitespace()
+            .map(|token| {
+                let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+                let value: u64 = value.parse().ok()?;
+                match unit {
+                    "h" => Some(value * 3600),
+                    "m" => Some(value * 60),
+                    "s" => Some(value),
+                    _ => None,
+                }
+            })
+            .sum::<Option<u64>>()
+            .map(Duration::from_secs);
+    }
+
+    let message = message.to_lowercase();
+    let start = message.find("please wait ")?;
+    let mut words = message[start + "please wait ".len()..].split_whitespace();
+
+    let value = match words.next()? {
+        "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "ten" => 10,
+        value => value.parse().ok()?,
+    };
+
+    match words.next()? {
+        "minute" | "minutes" => Some(Duration::from_secs(value * 60)),
+        "second" | "seconds" => Some(Duration::from_secs(value)),
+        _ => None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Downloads inputs and puzzle descriptions and submits answers.
+pub struct Client {
+    config: Config,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(config: Config) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self { config, agent }
+    }
+
+    /// Creates a client configured by [`Config::from_env`].
+    pub fn from_env() -> Result<Self, ClientError> {
+        Config::from_env().map(Self::new)
+    }
+
+    /// Downloads the personal input of a puzzle.
+    pub fn input(&self, puzzle: PuzzleId) -> Result<String, ClientError> {
+        let response = self
+            .agent
+            .get(&self.day_url(puzzle, "/input"))
+            .set("Cookie", &self.cookie())
+            .call()?;
+
+        Ok(response.into_string()?)
+    }
+
+    /// Downloads the description of a puzzle, converted to Markdown.
+    /// The description includes part two once part one is solved.
+    pub fn puzzle(&self, puzzle: PuzzleId) -> Result<String, ClientError> {
+        let response = self
+            .agent
+            .get(&self.day_url(puzzle, ""))
+            .set("Cookie", &self.cookie())
+            .call()?;
+
+        Ok(markdown::puzzle_to_markdown(&response.into_string()?))
+    }
+
+    /// Submits the answer of a part.
+    pub fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: Part,
+        answer: &str,
+    ) -> Result<Submission, ClientError> {
+        let response = self
+            .agent
+            .post(&self.day_url(puzzle, "/answer"))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+
+        Ok(Submission::from_html(&response.into_string()?))
+    }
+
+    fn day_url(&self, puzzle: PuzzleId, suffix: &str) -> String {
+        format!(
+            "{}/{}/day/{}{suffix}",
+            self.config.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.config.session)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+        time::Duration,
+    };
+
+    use super::{parse_wait, Client, ClientError, Config, Verdict};
+    use crate::{
+        day,
+        template::{Part, PuzzleId, Year},
+    };
+
+    fn puzzle(year: u16, day: crate::template::Day) -> PuzzleId {
+        PuzzleId::new(Year::new(year).unwrap(), day)
+    }
+
+    /// Serves the given responses in order and returns the requests it received.
+    fn mock_server(responses: Vec<(u16, &'static str)>) -> (Client, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|(status, body)| {
+                    let (stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream);
+
+                    let mut request = String::new();
+                    let mut content_length = 0;
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        if let Some(len) = line.to_lowercase().strip_prefix("content-length: ") {
+                            content_length = len.trim().parse().unwrap();
+                        }
+                        if line == "\r\n" {
+                            break;
+                        }
+                        request.push_str(&line);
+                    }
+
+                    let mut body_buf = vec![0; content_length];
+                    reader.read_exact(&mut body_buf).unwrap();
+                    request.push_str(&String::from_utf8(body_buf).unwrap());
+
+                    write!(
+                        reader.get_mut(),
+                        "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                    .unwrap();
+
+                    request
+                })
+                .collect()
+        });
+
+        let client = Client::new(Config {
+            session: "secret".into(),
+            base_url,
+        });
+
+        (client, handle)
+    }
+
+    #[test]
+    fn downloads_inputs() {
+        let (client, server) = mock_server(vec![(200, "1 2\n3 4\n")]);
+
+        assert_eq!(client.input(puzzle(2024, day!(1))).unwrap(), "1 2\n3 4\n");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2024/day/1/input HTTP/1.1"));
+        assert!(requests[0].contains("session=secret"));
+    }
+
+    #[test]
+    fn downloads_puzzles() {
+        let (client, server) = mock_server(vec![(
+            200,
+            "<main><article class=\"day-desc\"><h2>--- Day 9: Disk ---</h2><p>Hi.</p></article></main>",
+        )]);
+
+        assert_eq!(
+            client.puzzle(puzzle(2024, day!(9))).unwrap(),
+            "## --- Day 9: Disk ---\n\nHi."
+        );
+        assert!(server.join().unwrap()[0].starts_with("GET /2024/day/9 HTTP/1.1"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (client, server) = mock_server(vec![
+            (200, "<article><p>That's the right answer! You are <em>one gold star</em> closer.</p></article>"),
+            (200, "<article><p>That's not the right answer; your answer is too high. Please wait one minute before trying again.</p></article>"),
+            (200, "<article><p>You gave an answer too recently. You have 1m 5s left to wait.</p></article>"),
+        ]);
+
+        let submission = client
+            .submit(puzzle(2023, day!(9)), Part::Two, "2858")
+            .unwrap();
+        assert_eq!(submission.verdict, Verdict::Correct);
+        assert_eq!(submission.wait, None);
+
+        let submission = client
+            .submit(puzzle(2023, day!(9)), Part::Two, "9999")
+            .unwrap();
+        assert_eq!(submission.verdict, Verdict::TooHigh);
+        assert_eq!(submission.wait, Some(Duration::from_secs(60)));
+
+        let submission = client
+            .submit(puzzle(2023, day!(9)), Part::Two, "1")
+            .unwrap();
+        assert_eq!(submission.verdict, Verdict::RateLimited);
+        assert_eq!(submission.wait, Some(Duration::from_secs(65)));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2023/day/9/answer HTTP/1.1"));
+        assert!(requests[0].ends_with("level=2&answer=2858"));
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (client, server) = mock_server(vec![(404, "Not found")]);
+
+        assert!(matches!(
+            client.input(puzzle(2024, day!(25))),
+            Err(ClientError::BadStatus(404))
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn parses_wait_times() {
+        assert_eq!(
+            parse_wait("please wait 5 minutes before trying again."),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(
+            parse_wait("You have 30s left to wait."),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(parse_wait("That's the right answer!"), None);
+    }
+}
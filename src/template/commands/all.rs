@@ -1,5 +1,30 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    all_days, record::OutputFormat, registry::Executor, run_multi::run_multi, timeout::Timeouts,
+    Year,
+};
+
+/// Runs every day of a year, up to `jobs` of them concurrently.
+/// Exits with a non-zero code if any part panicked or exceeded its timeout.
+pub fn handle(
+    year: Year,
+    executor: Executor,
+    format: OutputFormat,
+    jobs: usize,
+    timeouts: &Timeouts,
+) {
+    let summary = run_multi(
+        year,
+        &all_days(year).collect(),
+        executor,
+        None,
+        format,
+        jobs,
+        timeouts,
+    );
+
+    if summary.has_failures() {
+        process::exit(1);
+    }
 }
@@ -1,14 +1,64 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::aoc_client::{Backend, Client, ClientError};
+use crate::template::{aoc_cli, PuzzleId};
+use std::{fs, io, path::Path, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+pub fn handle(puzzle: PuzzleId) {
+    if let Err(e) = create_data_dirs(puzzle) {
+        eprintln!("failed to create data folders: {e}");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if Backend::from_env() == Backend::AocCli {
+        if aoc_cli::check().is_err() {
+            eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+            process::exit(1);
+        }
+
+        if let Err(e) = aoc_cli::download(puzzle) {
+            eprintln!("failed to call aoc-cli: {e}");
+            process::exit(1);
+        };
+        return;
+    }
+
+    if let Err(e) = download(puzzle) {
+        eprintln!("failed to download {puzzle}: {e}");
         process::exit(1);
-    };
+    }
+}
+
+fn download(puzzle: PuzzleId) -> Result<(), ClientError> {
+    let client = Client::from_env()?;
+    let input_path = puzzle.data_file("inputs", "txt");
+    let puzzle_path = puzzle.data_file("puzzles", "md");
+
+    let input = client.input(puzzle)?;
+    let description = client.puzzle(puzzle)?;
+
+    fs::write(&input_path, input)?;
+    fs::write(&puzzle_path, description)?;
+
+    println!("---");
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+    Ok(())
+}
+
+/// Creates the folders of a year in `data/`, which do not exist before its first puzzle.
+pub fn create_data_dirs(puzzle: PuzzleId) -> io::Result<()> {
+    ["inputs", "examples", "puzzles"]
+        .iter()
+        .filter_map(|folder| {
+            puzzle
+                .data_file(folder, "txt")
+                .parent()
+                .map(Path::to_path_buf)
+        })
+        .try_for_each(fs::create_dir_all)
 }
@@ -1,6 +1,8 @@
 pub mod all;
 pub mod download;
 pub mod read;
+pub mod replay;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
+pub mod watch;
@@ -1,15 +1,37 @@
-use std::process;
+use std::{fs, process};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::aoc_client::{Backend, Client, ClientError};
+use crate::template::{aoc_cli, commands::download, PuzzleId};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+pub fn handle(puzzle: PuzzleId) {
+    if let Err(e) = download::create_data_dirs(puzzle) {
+        eprintln!("failed to create data folders: {e}");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if Backend::from_env() == Backend::AocCli {
+        if aoc_cli::check().is_err() {
+            eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+            process::exit(1);
+        }
+
+        if let Err(e) = aoc_cli::read(puzzle) {
+            eprintln!("failed to call aoc-cli: {e}");
+            process::exit(1);
+        };
+        return;
+    }
+
+    if let Err(e) = read(puzzle) {
+        eprintln!("failed to read {puzzle}: {e}");
         process::exit(1);
-    };
+    }
+}
+
+/// Fetches the puzzle description, stores it and prints it to the terminal.
+fn read(puzzle: PuzzleId) -> Result<(), ClientError> {
+    let description = Client::from_env()?.puzzle(puzzle)?;
+    fs::write(puzzle.data_file("puzzles", "md"), &description)?;
+    println!("{description}");
+    Ok(())
 }
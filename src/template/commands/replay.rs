@@ -0,0 +1,313 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::Path;
+use std::process::{self, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use crate::replay::{self, GifWriter, Replay};
+use crate::template::{PuzzleId, ANSI_BOLD, ANSI_RESET};
+use crate::viz::Frame;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReplayOptions {
+    /// Frames per second, changed with `+` and `-` while playing.
+    pub fps: u32,
+    /// Only every n-th frame is shown, to skip through long simulations.
+    pub every: usize,
+    /// Writes animated GIFs next to the recordings instead of playing them.
+    pub gif: bool,
+    /// Pixels per cell of GIFs.
+    pub scale: usize,
+    /// Replays the existing recordings without running the solution again.
+    pub no_run: bool,
+}
+
+impl Default for ReplayOptions {
+    fn default() -> Self {
+        Self {
+            fps: 10,
+            every: 1,
+            gif: false,
+            scale: 4,
+            no_run: false,
+        }
+    }
+}
+
+pub fn handle(puzzle: PuzzleId, options: ReplayOptions) {
+    if !options.no_run {
+        record(puzzle);
+    }
+
+    let recordings = match replay::recordings(puzzle) {
+        Ok(recordings) if !recordings.is_empty() => recordings,
+        Ok(_) | Err(_) => {
+            eprintln!("{puzzle} has no recordings. Record frames with `advent_of_code::replay::Recorder`.");
+            process::exit(1);
+        }
+    };
+
+    let result = if options.gif {
+        recordings
+            .iter()
+            .try_for_each(|path| export_gif(path, options))
+    } else {
+        play(&recordings, options)
+    };
+
+    if let Err(e) = result {
+        eprintln!("failed to replay {puzzle}: {e}");
+        process::exit(1);
+    }
+}
+
+/// Runs the solution with `--record`, its answers are discarded.
+fn record(puzzle: PuzzleId) {
+    let status = Command::new("cargo")
+        .args(["run", "--quiet", "--release", "--bin", &puzzle.bin_name()])
+        .args(["--", "--record"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::inherit())
+        .status();
+
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => {
+            eprintln!("recording {puzzle} failed: {status}");
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("failed to run cargo: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn export_gif(recording: &Path, options: ReplayOptions) -> io::Result<()> {
+    let path = recording.with_extension("gif");
+    let mut gif = GifWriter::new(
+        BufWriter::new(File::create(&path)?),
+        options.scale,
+        options.fps,
+    );
+
+    let (mut index, mut written) = (0, 0);
+    let mut frames = Replay::open(recording)?;
+    while let Some(frame) = frames.next_frame()? {
+        if index % options.every == 0 {
+            gif.frame(frame)?;
+            written += 1;
+        }
+        index += 1;
+    }
+    gif.finish()?;
+
+    println!("Wrote {written} frames to {}", path.display());
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+enum Control {
+    Continue,
+    /// The status changed while the frame is shown.
+    Redraw,
+    Skip,
+    Quit,
+}
+
+/// Plays the recordings one after another in the terminal.
+fn play(recordings: &[impl AsRef<Path>], options: ReplayOptions) -> io::Result<()> {
+    let terminal = Terminal::enter();
+    let keys = terminal.as_ref().map(|_| read_keys());
+    let (columns, rows) = terminal_size();
+
+    let mut player = Player {
+        fps: options.fps.max(1),
+        paused: false,
+        keys,
+    };
+
+    let mut result = Ok(());
+    'recordings: for recording in recordings {
+        let recording = recording.as_ref();
+        let name = recording
+            .file_stem()
+            .map_or_else(String::new, |stem| stem.to_string_lossy().into_owned());
+
+        let mut frames = match Replay::open(recording) {
+            Ok(frames) => frames,
+            Err(e) => {
+                result = Err(e);
+                break;
+            }
+        };
+
+        let mut index = 0;
+        loop {
+            let frame = match frames.next_frame() {
+                Ok(Some(frame)) => frame,
+                Ok(None) => break,
+                Err(e) => {
+                    result = Err(e);
+                    break 'recordings;
+                }
+            };
+            index += 1;
+            if (index - 1) % options.every != 0 {
+                continue;
+            }
+
+            loop {
+                // leave room for the status line
+                draw(
+                    frame,
+                    columns,
+                    rows.saturating_sub(1),
+                    &player.status(&name, index),
+                );
+                match player.wait() {
+                    Control::Continue => break,
+                    Control::Redraw => {}
+                    Control::Skip => continue 'recordings,
+                    Control::Quit => break 'recordings,
+                }
+            }
+        }
+    }
+
+    drop(terminal);
+    result
+}
+
+struct Player {
+    fps: u32,
+    paused: bool,
+    keys: Option<Receiver<u8>>,
+}
+
+impl Player {
+    fn status(&self, name: &str, index: usize) -> String {
+        let state = if self.paused { "paused" } else { "playing" };
+        format!(
+            "{ANSI_BOLD}{name}{ANSI_RESET} frame {index} ({state}, {} fps) \
+            [space] pause [n] step [+/-] speed [s] skip [q] quit",
+            self.fps
+        )
+    }
+
+    /// Waits for the next frame, handling the keys pressed meanwhile.
+    fn wait(&mut self) -> Control {
+        let delay = Duration::from_secs(1) / self.fps;
+        let Some(keys) = &self.keys else {
+            thread::sleep(delay);
+            return Control::Continue;
+        };
+
+        loop {
+            let key = if self.paused {
+                keys.recv().map_err(|_| RecvTimeoutError::Disconnected)
+            } else {
+                keys.recv_timeout(delay)
+            };
+
+            match key {
+                Ok(b' ') => {
+                    self.paused = !self.paused;
+                    return Control::Redraw;
+                }
+                Ok(b'n') if self.paused => return Control::Continue,
+                Ok(b'+') => {
+                    self.fps = (self.fps * 2).min(1000);
+                    return Control::Redraw;
+                }
+                Ok(b'-') => {
+                    self.fps = (self.fps / 2).max(1);
+                    return Control::Redraw;
+                }
+                Ok(b's') => return Control::Skip,
+                Ok(b'q') => return Control::Quit,
+                Ok(_) => {}
+                Err(RecvTimeoutError::Timeout) => return Control::Continue,
+                // stdin is closed, keep playing without controls
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.keys = None;
+                    self.paused = false;
+                    return Control::Continue;
+                }
+            }
+        }
+    }
+}
+
+fn draw(frame: &Frame, columns: usize, rows: usize, status: &str) {
+    let frame = frame.cropped(columns, rows);
+    let mut stdout = io::stdout().lock();
+    // move to the top left and clear the screen
+    let _ = write!(stdout, "\x1b[H\x1b[2J{}\n{status}", frame.to_ansi());
+    let _ = stdout.flush();
+}
+
+/// Reads single key presses from stdin on a background thread.
+fn read_keys() -> Receiver<u8> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for byte in io::stdin().lock().bytes() {
+            let Ok(byte) = byte else {
+                break;
+            };
+            if sender.send(byte).is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+/// The size of the terminal in columns and rows, 80x24 if it is unknown.
+fn terminal_size() -> (usize, usize) {
+    let size = stty(&["size"]).and_then(|size| {
+        let (rows, columns) = size.split_once(' ')?;
+        Some((columns.parse().ok()?, rows.parse().ok()?))
+    });
+    size.unwrap_or((80, 24))
+}
+
+/// Runs `stty` on the terminal of stdin, returns its output.
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Reads key presses without waiting for enter or echoing them, and hides the cursor. Restores the terminal on drop.
+struct Terminal {
+    settings: String,
+}
+
+impl Terminal {
+    /// [`None`] if stdin is not a terminal.
+    fn enter() -> Option<Self> {
+        let settings = stty(&["-g"])?;
+        stty(&["-icanon", "-echo", "min", "1"])?;
+        print!("\x1b[?25l");
+        Some(Self { settings })
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        stty(&[&self.settings]);
+        println!("\x1b[?25h");
+    }
+}
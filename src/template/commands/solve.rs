@@ -1,9 +1,57 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::commands::watch;
+use crate::template::input::InputSource;
+use crate::template::{record::OutputFormat, Part, PuzzleId};
+use crate::viz;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+#[derive(Debug, Clone, Default)]
+pub struct SolveOptions {
+    pub release: bool,
+    /// Profiles the heap with dhat instead of running in release mode.
+    pub dhat: bool,
+    pub submit: Option<Part>,
+    pub accept: bool,
+    pub format: OutputFormat,
+    pub viz: Option<viz::Output>,
+    /// Shows the debug logs of the solution.
+    pub verbose: bool,
+    /// Runs the tests and the solution again whenever its files change, see [`watch::handle`].
+    pub watch: bool,
+    pub input: InputSource,
+}
+
+pub fn handle(puzzle: PuzzleId, options: SolveOptions) {
+    if options.watch {
+        if options.submit.is_some() || options.accept || options.dhat {
+            eprintln!("`--submit`, `--accept` and `--dhat` cannot be combined with `--watch`.");
+            process::exit(1);
+        }
+        if options.input == InputSource::Stdin {
+            eprintln!("`--watch` reruns the solution on file changes, pass an input file instead of `--input -`.");
+            process::exit(1);
+        }
+        return watch::handle(puzzle, &options);
+    }
+
+    let SolveOptions {
+        release,
+        dhat,
+        submit,
+        accept,
+        format,
+        viz,
+        verbose,
+        watch: _,
+        input,
+    } = options;
+
+    if input != InputSource::Puzzle && (submit.is_some() || accept) {
+        eprintln!("Only answers for the puzzle input can be submitted or accepted, remove `--input` or `--example`.");
+        process::exit(1);
+    }
+
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.bin_name()];
 
     if dhat {
         cmd_args.extend([
@@ -18,11 +66,27 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
+    if let Some(submit_part) = submit {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
+    if accept {
+        cmd_args.push("--accept".to_string());
+    }
+
+    cmd_args.extend(format.to_args());
+
+    if let Some(viz) = viz {
+        cmd_args.extend(viz.to_args());
+    }
+
+    if verbose {
+        cmd_args.push("--verbose".to_string());
+    }
+
+    cmd_args.extend(input.to_args());
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -1,34 +1,92 @@
 use std::collections::HashSet;
+use std::process;
 
+use tinyjson::JsonValue;
+
+use crate::template::compare::{compare, has_regressions};
+use crate::template::record::OutputFormat;
+use crate::template::registry::Executor;
 use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::runner::BenchConfig;
+use crate::template::timeout::Timeouts;
+use crate::template::timings::{Timing, TimingHistory, TimingRun, Timings};
+use crate::template::{
+    all_days, civil_date, readme_benchmarks, Day, PuzzleId, Year, ANSI_BOLD, ANSI_RESET,
+};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Benches the selected days of a year. If a `compare_threshold` is passed, the fresh timings are compared
+/// against the stored ones and the process exits with a non-zero code on regressions.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    format: OutputFormat,
+    bench: BenchConfig,
+    compare_threshold: Option<f64>,
+    executor: Executor,
+) {
+    let stored_timings = Timings::read_from_file(year);
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
-                all_days().collect()
+                all_days(year).collect()
+            } else if compare_threshold.is_some() {
+                // when comparing, run every day that has a baseline.
+                stored_timings.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                all_days(year)
+                    .filter(|&day| !stored_timings.is_day_complete(PuzzleId::new(year, day)))
                     .collect()
             }
         },
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let summary = run_multi(
+        year,
+        &days_to_run,
+        executor,
+        Some(bench),
+        format,
+        1,
+        &Timeouts::default(),
+    );
+    let has_failed = summary.has_failures();
+    let timings = summary.timings.unwrap();
+
+    let has_regressed = compare_threshold.is_some_and(|threshold| {
+        let comparisons = compare(&stored_timings, &timings, threshold);
+
+        match format {
+            OutputFormat::Human => {
+                println!(
+                    "\n{ANSI_BOLD}Comparison{ANSI_RESET} (threshold {:.1}%)",
+                    threshold * 100.0
+                );
+                comparisons.iter().for_each(|c| println!("{c}"));
+            }
+            OutputFormat::Json => comparisons.iter().for_each(|c| {
+                println!("{}", JsonValue::from(c).stringify().unwrap());
+            }),
+        }
+
+        has_regressions(&comparisons)
+    });
 
     if store {
+        let mut history = TimingHistory::read_from_file(year);
+        history.append(TimingRun::now(timings.clone()));
+        history.store_file(year).unwrap();
+
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(year, merged_timings) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +95,89 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if has_regressed {
+        eprintln!("Detected performance regressions.");
+        process::exit(1);
+    }
+
+    if has_failed {
+        process::exit(1);
+    }
+}
+
+/// Prints every stored measurement of a puzzle, oldest first.
+pub fn handle_history(puzzle: PuzzleId, format: OutputFormat) {
+    let history = TimingHistory::read_from_file(puzzle.year);
+    let day = puzzle.day;
+
+    if format == OutputFormat::Json {
+        history.for_day(day).for_each(|(run, timing)| {
+            let run = TimingRun {
+                timings: Timings {
+                    data: vec![timing.clone()],
+                },
+                ..run.clone()
+            };
+            println!("{}", JsonValue::from(&run).stringify().unwrap());
+        });
+        return;
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+
+    let mut previous: Option<&Timing> = None;
+    for (run, timing) in history.for_day(day) {
+        let change = previous
+            .filter(|p| p.total_nanos > 0.0)
+            .map(|p| {
+                format!(
+                    " ({:+.1}%)",
+                    (timing.total_nanos / p.total_nanos - 1.0) * 100.0
+                )
+            })
+            .unwrap_or_default();
+
+        println!(
+            "{}  {:<16} Part 1: {:<10} Part 2: {:<10} Total: {:.2}ms{change}",
+            format_timestamp(run.timestamp),
+            run.commit.as_deref().unwrap_or("-"),
+            timing.part_1.as_deref().unwrap_or("-"),
+            timing.part_2.as_deref().unwrap_or("-"),
+            timing.total_nanos / 1_000_000_f64,
+        );
+        previous = Some(timing);
+    }
+
+    match (history.latest(day), history.best(day)) {
+        (Some((latest, _)), Some((best, best_timing))) => {
+            println!();
+            if std::ptr::eq(latest, best) {
+                println!("The latest run is the fastest.");
+            } else {
+                println!(
+                    "Fastest run: {} ({:.2}ms)",
+                    format_timestamp(best.timestamp),
+                    best_timing.total_nanos / 1_000_000_f64
+                );
+            }
+        }
+        _ => println!(
+            "No stored timings. Run `cargo time {day} --year {} --store` to record one.",
+            puzzle.year
+        ),
+    }
+}
+
+/// Formats seconds since the unix epoch as an UTC date and time.
+fn format_timestamp(timestamp: u64) -> String {
+    let (year, month, day) = civil_date(timestamp);
+    let secs = timestamp % 86400;
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        secs / 3600,
+        (secs % 3600) / 60
+    )
 }
@@ -0,0 +1,184 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::{self, Command};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::template::commands::solve::SolveOptions;
+use crate::template::input::InputSource;
+use crate::template::record::{OutputFormat, PartRecord};
+use crate::template::run_multi::child_commands;
+use crate::template::runner::format_record;
+use crate::template::timeout::Timeouts;
+use crate::template::{Part, PuzzleId, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::viz;
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Runs the tests and the solution of `puzzle` whenever its source, examples or input change, until interrupted.
+///
+/// The solution runs on the input and with the output options of `options`, which must not submit or profile.
+pub fn handle(puzzle: PuzzleId, options: &SolveOptions) {
+    let mut previous: Option<Vec<PartRecord>> = None;
+    let mut versions = modification_times(puzzle, &options.input);
+
+    loop {
+        // move to the top left and clear the screen
+        print!("\x1b[H\x1b[2J");
+        println!(
+            "{ANSI_BOLD}Watching {puzzle}{ANSI_RESET} {ANSI_ITALIC}(press ctrl-c to stop){ANSI_RESET}\n"
+        );
+        let _ = io::stdout().flush();
+
+        run_tests(puzzle);
+        let records = run_solution(puzzle, options);
+        if let (Some(previous), OutputFormat::Human) = (&previous, options.format) {
+            print!("{}", format_changes(puzzle, previous, &records));
+        }
+        previous = Some(records);
+
+        versions = wait_for_change(puzzle, &options.input, &versions);
+    }
+}
+
+fn run_tests(puzzle: PuzzleId) {
+    let status = Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &puzzle.bin_name()])
+        .status();
+
+    match status {
+        Ok(status) if status.success() => println!("Tests passed.\n"),
+        Ok(_) => println!("{ANSI_BOLD}Tests failed.{ANSI_RESET}\n"),
+        Err(e) => {
+            eprintln!("failed to run cargo: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn run_solution(puzzle: PuzzleId, options: &SolveOptions) -> Vec<PartRecord> {
+    let mut child_args = options.viz.map(viz::Output::to_args).unwrap_or_default();
+    if options.verbose {
+        child_args.push("--verbose".to_string());
+    }
+
+    let mut output = String::new();
+    let result = child_commands::run_solution(
+        puzzle,
+        &options.input,
+        &child_args,
+        None,
+        options.release,
+        options.format,
+        &Timeouts::default(),
+        &mut output,
+    );
+
+    print!("{output}");
+    match result {
+        Ok(records) => {
+            for record in &records {
+                match options.format {
+                    OutputFormat::Human => print!("{}", format_record(record)),
+                    OutputFormat::Json => println!("{}", record.to_json_line()),
+                }
+            }
+            records
+        }
+        Err(e) => {
+            eprintln!("failed to run {puzzle}: {e:?}");
+            vec![]
+        }
+    }
+}
+
+/// Lists the answers that differ from the previous run.
+fn format_changes(puzzle: PuzzleId, previous: &[PartRecord], records: &[PartRecord]) -> String {
+    let answer = |records: &[PartRecord], part: Part| {
+        records
+            .iter()
+            .find(|record| record.part == part)
+            .and_then(|record| record.answer.clone())
+    };
+
+    let changes: Vec<String> = puzzle
+        .parts()
+        .iter()
+        .filter_map(|&part| {
+            let (before, after) = (answer(previous, part), answer(records, part));
+            (before != after).then(|| {
+                let show = |answer: Option<String>| answer.unwrap_or_else(|| "✖".to_string());
+                format!(
+                    "Part {part}: {} → {ANSI_BOLD}{}{ANSI_RESET}\n",
+                    show(before),
+                    show(after)
+                )
+            })
+        })
+        .collect();
+
+    if changes.is_empty() {
+        format!("\n{ANSI_ITALIC}The answers did not change.{ANSI_RESET}\n")
+    } else {
+        format!("\nChanged answers:\n{}", changes.concat())
+    }
+}
+
+/// The source of the solution, the examples of `puzzle` that its tests read and the file of `input`.
+fn watched_files(puzzle: PuzzleId, input: &InputSource) -> Vec<PathBuf> {
+    let input = input.path(puzzle);
+    let example = puzzle.data_file("examples", "txt");
+
+    // further examples are named like `05-2.txt`
+    let prefix = format!("{}-", puzzle.day);
+    let examples = example
+        .parent()
+        .and_then(|dir| fs::read_dir(dir).ok())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".txt"))
+        });
+
+    [PathBuf::from(puzzle.bin_path()), example]
+        .into_iter()
+        .chain(examples)
+        .chain(input)
+        .collect()
+}
+
+fn modification_times(
+    puzzle: PuzzleId,
+    input: &InputSource,
+) -> BTreeMap<PathBuf, Option<SystemTime>> {
+    watched_files(puzzle, input)
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|metadata| metadata.modified());
+            (path, modified.ok())
+        })
+        .collect()
+}
+
+/// Waits until a watched file is created, changed or removed, returns the new modification times.
+fn wait_for_change(
+    puzzle: PuzzleId,
+    input: &InputSource,
+    versions: &BTreeMap<PathBuf, Option<SystemTime>>,
+) -> BTreeMap<PathBuf, Option<SystemTime>> {
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let current = modification_times(puzzle, input);
+        if &current != versions {
+            // editors often write a file in several steps, wait for them to finish
+            thread::sleep(POLL_INTERVAL);
+            return modification_times(puzzle, input);
+        }
+    }
+}
@@ -0,0 +1,275 @@
+/// Compares fresh benchmark timings against a stored baseline.
+use std::{collections::HashMap, fmt::Display, time::Duration};
+
+use tinyjson::JsonValue;
+
+use crate::template::stats::Stats;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Day, Part};
+
+/// Critical value of a two-sided test at a 95% confidence level.
+/// Benchmarks take at least 10 samples, so the normal approximation of Welch's t-test is good enough.
+const CRITICAL_VALUE: f64 = 1.96;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Regression,
+    Improvement,
+    Unchanged,
+    /// Either the baseline or the fresh run have no statistics for this part.
+    NoBaseline,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Regression => f.write_str("regression"),
+            Self::Improvement => f.write_str("improvement"),
+            Self::Unchanged => f.write_str("unchanged"),
+            Self::NoBaseline => f.write_str("no baseline"),
+        }
+    }
+}
+
+/// The comparison of a single part against its baseline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub day: Day,
+    pub part: Part,
+    /// Mean execution time of the baseline in nanoseconds.
+    pub baseline_nanos: Option<f64>,
+    /// Mean execution time of the fresh run in nanoseconds.
+    pub current_nanos: Option<f64>,
+    pub verdict: Verdict,
+}
+
+impl Comparison {
+    /// Relative change of the mean execution time, e.g. `0.1` for 10% slower.
+    pub fn change(&self) -> Option<f64> {
+        Some(self.current_nanos? / self.baseline_nanos? - 1.0)
+    }
+}
+
+/// Compares every part of `current` against the same part in `baseline`.
+///
+/// A change is only flagged if the means differ significantly and by more than `threshold` (e.g. `0.05` for 5%).
+pub fn compare(baseline: &Timings, current: &Timings, threshold: f64) -> Vec<Comparison> {
+    current
+        .data
+        .iter()
+        .flat_map(|timing| {
+            let base = baseline.data.iter().find(|t| t.day == timing.day);
+            Part::iter().filter_map(move |part| compare_part(timing, base, part, threshold))
+        })
+        .collect()
+}
+
+fn compare_part(
+    current: &Timing,
+    baseline: Option<&Timing>,
+    part: Part,
+    threshold: f64,
+) -> Option<Comparison> {
+    // skip parts that were not solved in the fresh run.
+    let current_stats = current.stats(part)?;
+    let baseline_stats = baseline.and_then(|b| b.stats(part));
+
+    let verdict = baseline_stats.map_or(Verdict::NoBaseline, |base| {
+        classify(base, current_stats, threshold)
+    });
+
+    Some(Comparison {
+        day: current.day,
+        part,
+        baseline_nanos: baseline_stats.map(|s| s.mean),
+        current_nanos: Some(current_stats.mean),
+        verdict,
+    })
+}
+
+fn classify(baseline: &Stats, current: &Stats, threshold: f64) -> Verdict {
+    if baseline.mean <= 0.0 {
+        return Verdict::NoBaseline;
+    }
+
+    let change = current.mean / baseline.mean - 1.0;
+    if change.abs() <= threshold {
+        return Verdict::Unchanged;
+    }
+
+    // Welch's t-test on the difference of the means.
+    #[allow(clippy::cast_precision_loss)]
+    let std_err = (baseline.std_dev.powi(2) / baseline.samples as f64
+        + current.std_dev.powi(2) / current.samples as f64)
+        .sqrt();
+    let is_significant =
+        std_err == 0.0 || (current.mean - baseline.mean).abs() / std_err > CRITICAL_VALUE;
+
+    match (is_significant, change > 0.0) {
+        (false, _) => Verdict::Unchanged,
+        (true, true) => Verdict::Regression,
+        (true, false) => Verdict::Improvement,
+    }
+}
+
+/// Returns `true` if any of the comparisons is a regression.
+pub fn has_regressions(comparisons: &[Comparison]) -> bool {
+    comparisons.iter().any(|c| c.verdict == Verdict::Regression)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let nanos = |x: Option<f64>| {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            x.map_or_else(
+                || "-".to_string(),
+                |x| format!("{:.1?}", Duration::from_nanos(x as u64)),
+            )
+        };
+
+        write!(
+            f,
+            "Day {} Part {}: {} -> {}",
+            self.day,
+            self.part,
+            nanos(self.baseline_nanos),
+            nanos(self.current_nanos)
+        )?;
+
+        if let Some(change) = self.change() {
+            write!(f, " ({:+.1}%)", change * 100.0)?;
+        }
+
+        write!(f, " {}", self.verdict)
+    }
+}
+
+impl From<&Comparison> for JsonValue {
+    fn from(value: &Comparison) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        let number = |x: Option<f64>| x.map_or(JsonValue::Null, JsonValue::Number);
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "part".into(),
+            JsonValue::Number(value.part.into_inner().into()),
+        );
+        map.insert("baseline_nanos".into(), number(value.baseline_nanos));
+        map.insert("current_nanos".into(), number(value.current_nanos));
+        map.insert("change".into(), number(value.change()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.to_string()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, has_regressions, Verdict};
+    use crate::{
+        day,
+        template::{
+            stats::{Outliers, Stats},
+            timings::{Timing, Timings},
+            Day,
+        },
+    };
+
+    fn stats(mean: f64, std_dev: f64) -> Stats {
+        Stats {
+            samples: 100,
+            mean,
+            median: mean,
+            min: mean,
+            max: mean,
+            std_dev,
+            p5: mean,
+            p25: mean,
+            p75: mean,
+            p95: mean,
+            p99: mean,
+            outliers: Outliers::default(),
+        }
+    }
+
+    fn timings(day: Day, part_1: Option<Stats>, part_2: Option<Stats>) -> Timings {
+        Timings {
+            data: vec![Timing {
+                day,
+                part_1: part_1.as_ref().map(|_| "1ms".into()),
+                part_2: part_2.as_ref().map(|_| "1ms".into()),
+                part_1_stats: part_1,
+                part_2_stats: part_2,
+                total_nanos: 0_f64,
+            }],
+        }
+    }
+
+    #[test]
+    fn detects_regressions() {
+        let baseline = timings(
+            day!(1),
+            Some(stats(1000.0, 10.0)),
+            Some(stats(1000.0, 10.0)),
+        );
+        let current = timings(
+            day!(1),
+            Some(stats(1500.0, 10.0)),
+            Some(stats(1000.0, 10.0)),
+        );
+        let res = compare(&baseline, &current, 0.05);
+
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].verdict, Verdict::Regression);
+        assert!((res[0].change().unwrap() - 0.5).abs() < 1e-9);
+        assert_eq!(res[1].verdict, Verdict::Unchanged);
+        assert!(has_regressions(&res));
+    }
+
+    #[test]
+    fn detects_improvements() {
+        let baseline = timings(day!(1), Some(stats(1000.0, 10.0)), None);
+        let current = timings(day!(1), Some(stats(500.0, 10.0)), None);
+        let res = compare(&baseline, &current, 0.05);
+
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].verdict, Verdict::Improvement);
+        assert!(!has_regressions(&res));
+    }
+
+    #[test]
+    fn ignores_changes_below_threshold() {
+        let baseline = timings(day!(1), Some(stats(1000.0, 1.0)), None);
+        let current = timings(day!(1), Some(stats(1040.0, 1.0)), None);
+        let res = compare(&baseline, &current, 0.05);
+
+        assert_eq!(res[0].verdict, Verdict::Unchanged);
+    }
+
+    #[test]
+    fn ignores_insignificant_changes() {
+        let baseline = timings(day!(1), Some(stats(1000.0, 2000.0)), None);
+        let current = timings(day!(1), Some(stats(1200.0, 2000.0)), None);
+        let res = compare(&baseline, &current, 0.05);
+
+        assert_eq!(res[0].verdict, Verdict::Unchanged);
+    }
+
+    #[test]
+    fn handles_missing_baselines() {
+        let baseline = timings(day!(2), Some(stats(1000.0, 10.0)), None);
+        let current = timings(day!(1), Some(stats(5000.0, 10.0)), None);
+        let res = compare(&baseline, &current, 0.05);
+
+        assert_eq!(res[0].verdict, Verdict::NoBaseline);
+        assert_eq!(res[0].change(), None);
+        assert!(!has_regressions(&res));
+    }
+}
@@ -0,0 +1,131 @@
+/// Errors returned by solutions and how they are reported.
+use std::{error::Error, fmt::Display};
+
+/// An error caused by a malformed line of the puzzle input.
+///
+/// Reports of this error (see [`report`]) quote the offending line and point at the column, if known.
+#[derive(Debug)]
+pub struct InputError {
+    /// The number of the line, starting at 1.
+    pub line: usize,
+    /// The column of the error in characters, starting at 1.
+    pub column: Option<usize>,
+    /// The content of the line.
+    pub text: String,
+    source: Box<dyn Error + Send + Sync>,
+}
+
+impl InputError {
+    /// Creates an error for line number `line` (starting at 1) with the content `text`.
+    pub fn new(line: usize, text: &str, source: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        Self {
+            line,
+            column: None,
+            text: text.to_string(),
+            source: source.into(),
+        }
+    }
+
+    /// Points the error at a column (in characters, starting at 1).
+    #[must_use]
+    pub fn at_column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// Quotes the line and points at the column, e.g.:
+    ///
+    /// ```text
+    ///  3 | 190: 10 x19
+    ///    |         ^
+    /// ```
+    pub fn snippet(&self) -> String {
+        let gutter = self.line.to_string().len();
+        let mut snippet = format!("{} | {}", self.line, self.text);
+
+        if let Some(column) = self.column {
+            snippet.push_str(&format!(
+                "\n{} | {}^",
+                " ".repeat(gutter),
+                " ".repeat(column.saturating_sub(1))
+            ));
+        }
+
+        snippet
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.column {
+            Some(column) => write!(f, "invalid input at line {}, column {column}", self.line),
+            None => write!(f, "invalid input at line {}", self.line),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+/// Formats an error with its chain of sources, one per line, e.g.:
+///
+/// ```text
+/// invalid input at line 3, column 9
+///   caused by: invalid digit found in string
+///  3 | 190: 10 x19
+///    |         ^
+/// ```
+pub fn report(error: &(dyn Error + 'static)) -> String {
+    let mut report = error.to_string();
+    let mut snippet = None;
+    let mut current = Some(error);
+
+    while let Some(error) = current {
+        if let Some(input_error) = error.downcast_ref::<InputError>() {
+            snippet.get_or_insert_with(|| input_error.snippet());
+        }
+        if let Some(source) = error.source() {
+            report.push_str(&format!("\n  caused by: {source}"));
+        }
+        current = error.source();
+    }
+
+    if let Some(snippet) = snippet {
+        report.push('\n');
+        report.push_str(&snippet);
+    }
+
+    report
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::num::ParseIntError;
+
+    use super::{report, InputError};
+
+    #[test]
+    fn reports_error_chains() {
+        let cause = "x19".parse::<u64>().unwrap_err();
+        let error = InputError::new(3, "190: 10 x19", cause).at_column(9);
+
+        assert_eq!(
+            report(&error),
+            "invalid input at line 3, column 9
+  caused by: invalid digit found in string
+3 | 190: 10 x19
+  |         ^"
+        );
+    }
+
+    #[test]
+    fn reports_plain_errors() {
+        let error: ParseIntError = "".parse::<u8>().unwrap_err();
+        assert_eq!(report(&error), "cannot parse integer from empty string");
+    }
+}
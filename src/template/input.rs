@@ -0,0 +1,114 @@
+/// Where a solution binary reads its puzzle input from.
+use std::fmt::Display;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+use crate::template::{data_path, PuzzleId};
+
+/// The input of a run of `cargo solve`, chosen with `--input <path>`, `--input -` or `--example [N]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The real input in `data/<year>/inputs/`.
+    #[default]
+    Puzzle,
+    /// The example in `data/<year>/examples/`, either the first one (e.g. `05.txt`) or a numbered one (`05-2.txt`).
+    Example(Option<u8>),
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Reads the `--input` and `--example` arguments passed to a solution binary.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        let value = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .map(|i| args.get(i + 1).map(String::as_str))
+        };
+
+        match (value("--input"), value("--example")) {
+            (Some(Some("-")), _) => Self::Stdin,
+            (Some(Some(path)), _) => Self::File(path.into()),
+            (_, Some(number)) => Self::Example(number.and_then(|number| number.parse().ok())),
+            _ => Self::Puzzle,
+        }
+    }
+
+    /// Arguments that mirror this input to a child invocation.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Puzzle => vec![],
+            Self::Example(None) => vec!["--example".into()],
+            Self::Example(Some(number)) => vec!["--example".into(), number.to_string()],
+            Self::File(path) => vec!["--input".into(), path.display().to_string()],
+            Self::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+
+    /// The file the input is read from, [`None`] for stdin.
+    pub fn path(&self, puzzle: PuzzleId) -> Option<PathBuf> {
+        match self {
+            Self::Puzzle => Some(puzzle.data_file("inputs", "txt")),
+            Self::Example(None) => Some(puzzle.data_file("examples", "txt")),
+            Self::Example(Some(number)) => Some(data_path(
+                puzzle.year,
+                Path::new("examples").join(format!("{}-{number}.txt", puzzle.day)),
+            )),
+            Self::File(path) => Some(path.clone()),
+            Self::Stdin => None,
+        }
+    }
+
+    pub fn read(&self, puzzle: PuzzleId) -> io::Result<String> {
+        match self.path(puzzle) {
+            Some(path) => fs::read_to_string(path),
+            None => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+
+    /// Describes the input for error messages, e.g. `data/2024/inputs/05.txt`.
+    pub fn describe(&self, puzzle: PuzzleId) -> impl Display {
+        self.path(puzzle)
+            .map_or("stdin".to_string(), |path| path.display().to_string())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::InputSource;
+    use crate::day;
+    use crate::template::{PuzzleId, Year};
+
+    #[test]
+    fn locates_examples() {
+        let puzzle = PuzzleId::new(Year::new(2023).unwrap(), day!(5));
+        let path = |input: InputSource| input.path(puzzle).unwrap();
+
+        assert!(path(InputSource::Puzzle).ends_with("inputs/05.txt"));
+        assert!(path(InputSource::Example(None)).ends_with("examples/05.txt"));
+        assert!(path(InputSource::Example(Some(2))).ends_with("examples/05-2.txt"));
+        assert_eq!(InputSource::Stdin.path(puzzle), None);
+        assert_eq!(InputSource::Stdin.describe(puzzle).to_string(), "stdin");
+    }
+
+    #[test]
+    fn mirrors_arguments() {
+        assert!(InputSource::Puzzle.to_args().is_empty());
+        assert_eq!(InputSource::Example(Some(2)).to_args(), ["--example", "2"]);
+        assert_eq!(InputSource::Stdin.to_args(), ["--input", "-"]);
+        assert_eq!(
+            InputSource::File(PathBuf::from("friend.txt")).to_args(),
+            ["--input", "friend.txt"]
+        );
+    }
+}
@@ -0,0 +1,75 @@
+/// Debug logging of solutions with the [`log`] macros, e.g. `log::debug!("stopped at {position:?}")`.
+///
+/// Logs go to stderr and are off unless the solution runs with `--verbose` (debug logs of the solution) or with
+/// `RUST_LOG` set, which takes precedence and accepts the [`env_logger`] filter syntax, e.g. `RUST_LOG=06=trace`.
+/// While a part is benchmarked, logs of its thread are dropped, so only the first run of a part is logged.
+use std::cell::Cell;
+use std::env;
+
+use env_logger::{Env, Logger};
+use log::{Log, Metadata, Record};
+
+thread_local! {
+    /// Whether logs of this thread are dropped, see [`muted`].
+    static MUTED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Forwards logs to [`env_logger`] unless they are [`muted`].
+struct MutableLogger(Logger);
+
+impl Log for MutableLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        !MUTED.get() && self.0.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        // checked first, so the arguments of muted logs are never formatted
+        if !MUTED.get() {
+            self.0.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.0.flush();
+    }
+}
+
+/// Whether the arguments of the process contain `--verbose`.
+pub fn is_verbose() -> bool {
+    env::args().any(|arg| arg == "--verbose")
+}
+
+/// Sets up the logger for the crate `crate_name`, the solution binary or `advent_of_code` for in-process runs.
+///
+/// Does nothing if a logger is set up already.
+pub fn init(crate_name: &str) {
+    let default = if is_verbose() {
+        format!("{crate_name}=debug")
+    } else {
+        "off".to_string()
+    };
+
+    let logger = env_logger::Builder::from_env(Env::default().default_filter_or(default))
+        .format_timestamp(None)
+        .build();
+    let max_level = logger.filter();
+
+    if log::set_boxed_logger(Box::new(MutableLogger(logger))).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
+
+/// Runs `f` while dropping the logs of this thread.
+pub fn muted<T>(f: impl FnOnce() -> T) -> T {
+    /// Unmutes on drop, so a panicking solution does not mute later ones.
+    struct Unmute(bool);
+
+    impl Drop for Unmute {
+        fn drop(&mut self) {
+            MUTED.set(self.0);
+        }
+    }
+
+    let _unmute = Unmute(MUTED.replace(true));
+    f()
+}
@@ -0,0 +1,234 @@
+/// Converts every `<article>` of a puzzle page to Markdown, separated by blank lines.
+pub fn puzzle_to_markdown(html: &str) -> String {
+    articles(html)
+        .map(to_markdown)
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Iterates over the inner HTML of every `<article>` element.
+pub fn articles(html: &str) -> impl Iterator<Item = &str> {
+    html.split("<article").skip(1).filter_map(|article| {
+        let start = article.find('>')? + 1;
+        let end = article.find("</article>")?;
+        article.get(start..end)
+    })
+}
+
+/// Converts an HTML fragment to Markdown.
+///
+/// This only understands the handful of tags used on adventofcode.com, anything else is reduced to its text.
+pub fn to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    // the target of every open link.
+    let mut links: Vec<Option<String>> = vec![];
+    let mut in_pre = false;
+    let mut in_code = false;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            push_text(&mut out, rest, in_pre);
+            break;
+        };
+
+        push_text(&mut out, &rest[..tag_start], in_pre);
+
+        let Some(tag_end) = rest[tag_start..].find('>').map(|i| tag_start + i) else {
+            push_text(&mut out, &rest[tag_start..], in_pre);
+            break;
+        };
+
+        let tag = &rest[tag_start + 1..tag_end];
+        rest = &rest[tag_end + 1..];
+
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match (name.as_str(), is_closing) {
+            ("h2", false) => {
+                end_block(&mut out);
+                out.push_str("## ");
+            }
+            ("h2" | "p" | "ul", _) => end_block(&mut out),
+            ("li", false) => {
+                if !out.is_empty() && !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("- ");
+            }
+            ("li", true) => out.push('\n'),
+            ("pre", false) => {
+                end_block(&mut out);
+                out.push_str("```\n");
+                in_pre = true;
+            }
+            ("pre", true) => {
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+                in_pre = false;
+            }
+            ("code", _) if !in_pre => {
+                out.push('`');
+                in_code = !is_closing;
+            }
+            // emphasis can not be nested in inline code.
+            ("em", _) if !in_pre && !in_code => out.push('*'),
+            ("br", _) => out.push('\n'),
+            ("a", false) => {
+                links.push(attribute(tag, "href"));
+                out.push('[');
+            }
+            ("a", true) => match links.pop().flatten() {
+                Some(href) => out.push_str(&format!("]({href})")),
+                None => out.push(']'),
+            },
+            _ => {}
+        }
+    }
+
+    out.trim().to_string()
+}
+
+/// Separates blocks like paragraphs by a single blank line.
+fn end_block(out: &mut String) {
+    out.truncate(out.trim_end().len());
+    if !out.is_empty() {
+        out.push_str("\n\n");
+    }
+}
+
+fn push_text(out: &mut String, text: &str, in_pre: bool) {
+    let text = decode_entities(text);
+    if in_pre {
+        out.push_str(&text);
+    } else {
+        // outside of preformatted text, whitespace is collapsed like a browser would.
+        let mut last_was_space = out.ends_with([' ', '\n']) || out.is_empty();
+        for c in text.chars() {
+            if c.is_whitespace() {
+                if !last_was_space {
+                    out.push(' ');
+                }
+                last_was_space = true;
+            } else {
+                out.push(c);
+                last_was_space = false;
+            }
+        }
+    }
+}
+
+/// Reads the value of an attribute from the inside of a tag, e.g. `a href="/2024"`.
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = tag[start..].find('"')? + start;
+    Some(decode_entities(&tag[start..end]))
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let c = match &rest[1..end] {
+                "amp" => '&',
+                "lt" => '<',
+                "gt" => '>',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => ' ',
+                entity => {
+                    let code = entity.strip_prefix('#')?;
+                    let code = match code.strip_prefix(['x', 'X']) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => code.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{puzzle_to_markdown, to_markdown};
+
+    #[test]
+    fn converts_puzzle_descriptions() {
+        let html = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>The <em>Chief Historian</em> is always present.</p>
+<p>For example:</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<ul>
+<li>The smallest is <code>1</code>.</li>
+<li>Then <code><em>11</em></code>.</li>
+</ul>
+<p>See <a href="/2024/about" target="_blank">about</a> &amp; more.</p>
+</article>
+<p>Your puzzle answer was <code>11</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Done.</p></article>
+</main>"#;
+
+        let expected = "## --- Day 1: Historian Hysteria ---
+
+The *Chief Historian* is always present.
+
+For example:
+
+```
+3   4
+4   3
+```
+
+- The smallest is `1`.
+- Then `11`.
+
+See [about](/2024/about) & more.
+
+## --- Part Two ---
+
+Done.";
+
+        assert_eq!(puzzle_to_markdown(html), expected);
+    }
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            to_markdown("<p>&lt;a&gt; &quot;b&quot; &#39;c&#x27; &amp;d</p>"),
+            "<a> \"b\" 'c' &d"
+        );
+    }
+}
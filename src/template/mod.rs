@@ -1,68 +1,120 @@
-use std::{env, fs};
+use std::{fs, path::Path};
 
+pub mod answers;
 pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod error;
+pub mod input;
+pub mod logging;
+pub mod record;
+pub mod registry;
 pub mod runner;
+pub mod stats;
+pub mod submissions;
+pub mod timeout;
 
 pub use day::*;
+pub use part::*;
+pub use year::*;
 
+mod compare;
 mod day;
+mod markdown;
+mod part;
 mod readme_benchmarks;
 mod run_multi;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file of a puzzle to a string, e.g. `data/2024/inputs/01.txt`.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
+    let f = fs::read_to_string(puzzle.data_file(folder, "txt"));
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+///
+/// The suffix is either a [`Part`] or the number of any further example file.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: impl Into<u8>) -> String {
+    let path = Path::new(folder).join(format!("{}-{}.txt", puzzle.day, part.into()));
+    let f = fs::read_to_string(data_path(puzzle.year, path));
     f.expect("could not open input file")
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
+/// The year of the solution is taken from the name of its source file, see [`PuzzleId::from_source_file`].
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Parts that the puzzle does not have (see [`PuzzleId::parts`]) are never run.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [part_one, One] [part_two, Two]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [part_one, One]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [part_two, Two]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, $( [$func:expr, $part:ident] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The puzzle solved by this binary.
+        ///
+        /// Read from the source file rather than `CARGO_BIN_NAME`, which is the main binary when the registry
+        /// runs the solution in-process.
+        #[allow(dead_code)]
+        fn puzzle() -> $crate::template::PuzzleId {
+            $crate::template::PuzzleId::from_source_file(file!(), DAY)
+        }
+
+        /// The parts of this day, used to run the solution in-process.
+        /// The registry sets the year for solutions that are not part of the configured year.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution {
+                year: None,
+                day: DAY,
+                parts: &[$(
+                    ($crate::template::Part::$part, |input, puzzle, bench| {
+                        $crate::template::runner::measure_part(
+                            $func,
+                            input,
+                            puzzle,
+                            $crate::template::Part::$part,
+                            bench,
+                        )
+                    }),
+                )*],
+            };
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            $crate::template::logging::init(env!("CARGO_CRATE_NAME"));
+            let source = $crate::template::input::InputSource::from_args();
+            let input = source.read(puzzle()).unwrap_or_else(|e| {
+                eprintln!("Could not read input of {} from {}: {e}", puzzle(), source.describe(puzzle()));
+                std::process::exit(1);
+            });
+            $(
+                if puzzle().has_part($crate::template::Part::$part) {
+                    run_part($func, &input, puzzle(), $crate::template::Part::$part);
+                }
+            )*
         }
     };
 }
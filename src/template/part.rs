@@ -0,0 +1,137 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::PuzzleId;
+
+/// A part of a puzzle, every puzzle has a part one and most have a part two.
+///
+/// # Display
+/// This value displays as its number.
+///
+/// ```
+/// # use advent_of_code::template::Part;
+/// assert_eq!(Part::Two.to_string(), "2")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One = 1,
+    Two = 2,
+}
+
+impl Part {
+    /// Both parts, in order.
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    /// Creates a [`Part`] from its number (1 or 2), returns [`None`] otherwise.
+    pub fn new(part: u8) -> Option<Self> {
+        match part {
+            1 => Some(Self::One),
+            2 => Some(Self::Two),
+            _ => None,
+        }
+    }
+
+    /// Converts the [`Part`] into its number.
+    pub fn into_inner(self) -> u8 {
+        self as u8
+    }
+
+    /// Iterates over both parts, in order.
+    pub fn iter() -> impl Iterator<Item = Part> {
+        Self::ALL.into_iter()
+    }
+
+    /// The part after this one, if any.
+    pub fn next(self) -> Option<Self> {
+        Self::new(self.into_inner() + 1)
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.into_inner())
+    }
+}
+
+impl From<Part> for u8 {
+    fn from(part: Part) -> Self {
+        part.into_inner()
+    }
+}
+
+impl FromStr for Part {
+    type Err = PartFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let part = s.parse().map_err(|_| PartFromStrError)?;
+        Self::new(part).ok_or(PartFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Part`].
+#[derive(Debug)]
+pub struct PartFromStrError;
+
+impl Error for PartFromStrError {}
+
+impl Display for PartFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a part number of 1 or 2")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl PuzzleId {
+    /// The parts of this puzzle. The last puzzle of an event only has a single part,
+    /// the second star is awarded for completing all other puzzles.
+    pub fn parts(&self) -> &'static [Part] {
+        if self.day == self.year.last_day() {
+            &[Part::One]
+        } else {
+            &Part::ALL
+        }
+    }
+
+    /// Returns `true` if this puzzle has the given part.
+    pub fn has_part(&self, part: Part) -> bool {
+        self.parts().contains(&part)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Part;
+    use crate::day;
+    use crate::template::{PuzzleId, Year};
+
+    #[test]
+    fn parses_parts() {
+        assert_eq!("1".parse::<Part>().unwrap(), Part::One);
+        assert_eq!("2".parse::<Part>().unwrap(), Part::Two);
+        assert!("0".parse::<Part>().is_err());
+        assert!("3".parse::<Part>().is_err());
+        assert!("two".parse::<Part>().is_err());
+    }
+
+    #[test]
+    fn iterates_parts() {
+        assert_eq!(Part::iter().collect::<Vec<_>>(), vec![Part::One, Part::Two]);
+        assert_eq!(Part::One.next(), Some(Part::Two));
+        assert_eq!(Part::Two.next(), None);
+    }
+
+    #[test]
+    fn knows_single_part_days() {
+        let puzzle = |year, day| PuzzleId::new(Year::new(year).unwrap(), day);
+
+        assert_eq!(puzzle(2024, day!(24)).parts(), &[Part::One, Part::Two]);
+        assert_eq!(puzzle(2024, day!(25)).parts(), &[Part::One]);
+        assert!(!puzzle(2024, day!(25)).has_part(Part::Two));
+        assert_eq!(puzzle(2025, day!(11)).parts(), &[Part::One, Part::Two]);
+        assert_eq!(puzzle(2025, day!(12)).parts(), &[Part::One]);
+    }
+}
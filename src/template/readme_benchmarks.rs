@@ -3,10 +3,16 @@
 use std::{fs, io};
 
 use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::{Part, PuzzleId, Year};
 
+/// Surrounds the table of the configured year.
 static MARKER: &str = "<!--- benchmarking table --->";
 
+/// Surrounds the table of another year, e.g. `<!--- benchmarking table 2023 --->`.
+fn year_marker(year: Year) -> String {
+    format!("<!--- benchmarking table {year} --->")
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -25,13 +31,8 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,11 +53,15 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
-
+fn construct_table(
+    marker: &str,
+    header: String,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+) -> String {
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker.into(),
         header,
         String::new(),
         "| Day | Part 1 | Part 2 |".into(),
@@ -64,35 +69,60 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
     ];
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        let puzzle = PuzzleId::new(year, timing.day);
+        let path = puzzle.bin_path();
+        // the last puzzle of an event has no second part, which is not the same as an unsolved one.
+        let cell = |part| match timing.part(part) {
+            _ if !puzzle.has_part(part) => "n/a".to_string(),
+            Some(time) => format!("`{time}`"),
+            None => "`-`".to_string(),
+        };
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | {} | {} |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            cell(Part::One),
+            cell(Part::Two)
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+/// Replaces the table of a year. The configured year may use the unscoped marker, the table of any other
+/// year is appended to the readme if it is not present yet.
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+) -> Result<(), Error> {
+    let year_marker = year_marker(year);
+
+    let (marker, header) = if s.contains(&year_marker) {
+        (year_marker.as_str(), format!("## {year} Benchmarks"))
+    } else if year == Year::configured() {
+        (MARKER, "## Benchmarks".into())
+    } else {
+        s.push_str(&format!("\n{year_marker}{year_marker}\n"));
+        (year_marker.as_str(), format!("## {year} Benchmarks"))
+    };
+
+    let positions = locate_table(s, marker)?;
+    let table = construct_table(marker, header, year, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+pub fn update(year: Year, timings: Timings) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, year, timings, total_millis)?;
     fs::write(path, &readme)?;
     Ok(())
 }
@@ -100,7 +130,11 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+        template::Year,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -109,18 +143,24 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 9e+10,
                 },
             ],
@@ -131,28 +171,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::configured(), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::configured(), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::configured(), get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::configured(), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::configured(), get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +200,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::configured(), get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -180,4 +220,30 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn appends_benchmarks_of_other_years() {
+        let year = Year::new(2017).unwrap();
+        let mut s = format!("foo\n{}{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, year, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year, get_mock_timings(), 190.0).unwrap();
+
+        assert_eq!(s.matches("## 2017 Benchmarks").count(), 1);
+        assert_eq!(s.matches("<!--- benchmarking table 2017 --->").count(), 2);
+        assert!(s.contains("| [Day 1](./src/bin/2017_01.rs) | `10ms` | `20ms` |"));
+        assert!(s.starts_with(&format!("foo\n{}{}\nbaz", MARKER, MARKER)));
+    }
+
+    #[test]
+    fn formats_single_part_days() {
+        let mut timings = get_mock_timings();
+        timings.data[2].day = Year::configured().last_day();
+        timings.data[2].part_2 = None;
+        timings.data[1].part_2 = None;
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, Year::configured(), timings, 190.0).unwrap();
+        assert!(s.contains("| `30ms` | `-` |"));
+        assert!(s.contains("| `40ms` | n/a |"));
+    }
 }
@@ -0,0 +1,451 @@
+/// Machine-readable output of solution runs.
+use std::{collections::HashMap, error::Error, fmt::Display, str::FromStr, time::Duration};
+
+use tinyjson::JsonValue;
+
+use crate::template::{answers::Check, stats::Stats, Day, Part};
+
+/// Output format of the `solve`, `all` and `time` commands.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human readable, ANSI-decorated lines like `Part 1: 42 (1.2ms @ 100 samples)`.
+    #[default]
+    Human,
+    /// One JSON record per line and part, see [`PartRecord`].
+    Json,
+}
+
+impl OutputFormat {
+    /// Reads the `--format` argument passed to a solution binary, defaulting to [`OutputFormat::Human`].
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+
+        args.iter()
+            .position(|x| x == "--format")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|format| format.parse().ok())
+            .unwrap_or_default()
+    }
+
+    /// Arguments that mirror this format to a child invocation.
+    pub fn to_args(self) -> Vec<String> {
+        match self {
+            Self::Human => vec![],
+            Self::Json => vec!["--format".into(), self.to_string()],
+        }
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Human => f.write_str("human"),
+            Self::Json => f.write_str("json"),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = OutputFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            _ => Err(OutputFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`OutputFormat`].
+#[derive(Debug)]
+pub struct OutputFormatFromStrError;
+
+impl Error for OutputFormatFromStrError {}
+
+impl Display for OutputFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting an output format of either `human` or `json`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The reason a part did not run to completion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Failure {
+    /// The part exceeded its timeout and was abandoned.
+    TimedOut,
+    /// The part panicked, or the process running it crashed.
+    Panicked,
+    /// The part returned an error, e.g. because the input is malformed.
+    Errored,
+}
+
+impl Failure {
+    /// Returns `true` if the remaining parts of a day can not run after this failure.
+    pub fn is_fatal(self) -> bool {
+        matches!(self, Self::TimedOut | Self::Panicked)
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TimedOut => f.write_str("timed out"),
+            Self::Panicked => f.write_str("panicked"),
+            Self::Errored => f.write_str("returned an error"),
+        }
+    }
+}
+
+impl FromStr for Failure {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "timed out" => Ok(Self::TimedOut),
+            "panicked" => Ok(Self::Panicked),
+            "returned an error" => Ok(Self::Errored),
+            _ => Err(format!("unknown failure `{s}`")),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The outcome of running a single part of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartRecord {
+    pub day: Day,
+    pub part: Part,
+    pub answer: Option<String>,
+    /// Average execution time in nanoseconds.
+    pub time_nanos: f64,
+    pub samples: u128,
+    /// Benchmark statistics, only present for timed runs.
+    pub stats: Option<Stats>,
+    /// The accepted answer of this part, if known.
+    pub expected: Option<String>,
+    /// Set if the part did not run to completion.
+    pub failure: Option<Failure>,
+    /// Details of the failure, e.g. the panic message or the report of the returned error.
+    pub error: Option<String>,
+}
+
+impl PartRecord {
+    /// Creates the record of a part that failed after running for `elapsed`.
+    pub fn failed(
+        day: Day,
+        part: Part,
+        failure: Failure,
+        error: Option<String>,
+        elapsed: Duration,
+    ) -> Self {
+        Self {
+            day,
+            part,
+            answer: None,
+            #[allow(clippy::cast_precision_loss)]
+            time_nanos: elapsed.as_nanos() as f64,
+            samples: 1,
+            stats: None,
+            expected: None,
+            failure: Some(failure),
+            error,
+        }
+    }
+
+    /// Verifies the answer against the accepted one. Returns `None` if there is no answer.
+    pub fn check(&self) -> Option<Check> {
+        let answer = self.answer.as_ref()?;
+        Some(Check::new(answer, self.expected.as_deref()))
+    }
+
+    /// Returns `true` if the part did not run to completion or produced a wrong answer.
+    pub fn is_failure(&self) -> bool {
+        self.failure.is_some() || matches!(self.check(), Some(Check::Wrong { .. }))
+    }
+
+    pub fn duration(&self) -> Duration {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Duration::from_nanos(self.time_nanos as u64)
+    }
+
+    /// Serializes the record to a single line of JSON.
+    pub fn to_json_line(&self) -> String {
+        JsonValue::from(self)
+            .stringify()
+            .expect("records should only contain finite numbers")
+    }
+
+    /// Parses a single line of JSON as emitted by [`PartRecord::to_json_line`].
+    pub fn from_json_line(line: &str) -> Result<Self, String> {
+        let json = JsonValue::from_str(line).or(Err("not a valid JSON line."))?;
+        Self::try_from(&json)
+    }
+}
+
+impl From<&PartRecord> for JsonValue {
+    fn from(value: &PartRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "part".into(),
+            JsonValue::Number(value.part.into_inner().into()),
+        );
+        map.insert(
+            "answer".into(),
+            value
+                .answer
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("time_nanos".into(), JsonValue::Number(value.time_nanos));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "stats".into(),
+            value
+                .stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "expected".into(),
+            value
+                .expected
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "failure".into(),
+            value
+                .failure
+                .map_or(JsonValue::Null, |f| JsonValue::String(f.to_string())),
+        );
+        map.insert(
+            "error".into(),
+            value
+                .error
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartRecord {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected record.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .and_then(|part| Part::iter().find(|p| f64::from(p.into_inner()) == *part))
+            .ok_or("Expected record.part to be either 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.answer to be null or string.")?;
+
+        let time_nanos = json
+            .get("time_nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected record.time_nanos to be a number.")?;
+
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected record.samples to be a number.")?;
+
+        let stats = match json.get("stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(Stats::try_from(v)?),
+        };
+
+        let expected = match json.get("expected") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected record.expected to be null or string.")?
+                    .clone(),
+            ),
+        };
+
+        let failure = match json.get("failure") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected record.failure to be null or string.")?
+                    .parse::<Failure>()?,
+            ),
+        };
+
+        let error = json
+            .get("error")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.error to be null or string.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(PartRecord {
+            day,
+            part,
+            answer: answer.cloned(),
+            time_nanos,
+            samples: samples as u128,
+            stats,
+            expected,
+            failure,
+            error: error.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Failure, OutputFormat, PartRecord};
+    use crate::template::answers::Check;
+    use crate::{
+        day,
+        template::{stats::Stats, Part},
+    };
+
+    fn get_mock_record() -> PartRecord {
+        PartRecord {
+            day: day!(3),
+            part: Part::Two,
+            answer: Some("42".into()),
+            time_nanos: 1250.0,
+            samples: 100,
+            stats: None,
+            expected: None,
+            failure: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn parses_output_format() {
+        assert_eq!("json".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+        assert_eq!(
+            "human".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Human
+        );
+        assert!("yaml".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn roundtrips_records() {
+        let record = get_mock_record();
+        let line = record.to_json_line();
+        assert!(!line.contains('\n'));
+        assert_eq!(PartRecord::from_json_line(&line).unwrap(), record);
+    }
+
+    #[test]
+    fn roundtrips_multiline_answers() {
+        let record = PartRecord {
+            answer: Some("#..\n.#.".into()),
+            ..get_mock_record()
+        };
+        let line = record.to_json_line();
+        assert!(!line.contains('\n'));
+        assert_eq!(PartRecord::from_json_line(&line).unwrap(), record);
+    }
+
+    #[test]
+    fn roundtrips_stats() {
+        let record = PartRecord {
+            stats: Stats::from_samples(&[Duration::from_nanos(1000), Duration::from_nanos(1500)]),
+            ..get_mock_record()
+        };
+        let line = record.to_json_line();
+        assert_eq!(PartRecord::from_json_line(&line).unwrap(), record);
+    }
+
+    #[test]
+    fn handles_unsolved_parts() {
+        let line = r#"{ "day": "01", "part": 1, "answer": null, "time_nanos": 0, "samples": 1, "error": null }"#;
+        let record = PartRecord::from_json_line(line).unwrap();
+        assert_eq!(record.answer, None);
+        assert_eq!(record.failure, None);
+        assert_eq!(record.error, None);
+    }
+
+    #[test]
+    fn checks_answers() {
+        let record = PartRecord {
+            expected: Some("41".into()),
+            ..get_mock_record()
+        };
+        assert!(record.is_failure());
+        assert_eq!(
+            PartRecord::from_json_line(&record.to_json_line()).unwrap(),
+            record
+        );
+
+        let record = PartRecord {
+            expected: Some("42".into()),
+            ..get_mock_record()
+        };
+        assert_eq!(record.check(), Some(Check::Correct));
+        assert!(!record.is_failure());
+        assert_eq!(get_mock_record().check(), Some(Check::Unknown));
+    }
+
+    #[test]
+    fn roundtrips_failures() {
+        let record = PartRecord::failed(
+            day!(6),
+            Part::Two,
+            Failure::Panicked,
+            Some("attempt to add with overflow".into()),
+            Duration::from_millis(3),
+        );
+        let line = record.to_json_line();
+        assert_eq!(PartRecord::from_json_line(&line).unwrap(), record);
+
+        let line = line.replace("panicked", "exploded");
+        assert!(PartRecord::from_json_line(&line).is_err());
+
+        let record = PartRecord::failed(
+            day!(7),
+            Part::One,
+            Failure::Errored,
+            Some("invalid input at line 3\n  caused by: invalid digit found in string".into()),
+            Duration::from_millis(1),
+        );
+        assert_eq!(
+            PartRecord::from_json_line(&record.to_json_line()).unwrap(),
+            record
+        );
+        assert!(!Failure::Errored.is_fatal());
+    }
+
+    #[test]
+    fn rejects_human_output() {
+        assert!(PartRecord::from_json_line("Part 1: 42 (1.2ms @ 100 samples)").is_err());
+        assert!(PartRecord::from_json_line(r#"{ "day": "01", "part": 3 }"#).is_err());
+    }
+}
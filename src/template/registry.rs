@@ -0,0 +1,141 @@
+/// Registry of solutions that can be run inside the current process.
+///
+/// Every `solution!` invocation exports a [`Solution`] constant named `SOLUTION`. The main binary
+/// includes all modules in `src/bin/` (see `build.rs`) and collects these constants, so `cargo all`
+/// and `cargo time` do not need to spawn a child process per day.
+use std::{
+    any::Any,
+    fs, io, panic,
+    sync::{mpsc, Arc},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::template::{
+    record::{Failure, PartRecord},
+    runner::BenchConfig,
+    timeout::Timeouts,
+    Day, Part, PuzzleId, Year,
+};
+
+/// Stack size of the threads running solution parts, matching the main thread on most platforms.
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Determines how `run_multi` executes solutions.
+#[derive(Clone, Copy, Debug)]
+pub enum Executor<'a> {
+    /// Run the registered solutions inside the current process.
+    InProcess(&'a [Solution]),
+    /// Spawn `cargo run --bin <day>` for every day.
+    Isolated { is_release: bool },
+}
+
+/// Runs a single part on the given input, benching it if a [`BenchConfig`] is passed.
+pub type PartFn = fn(&str, PuzzleId, Option<BenchConfig>) -> PartRecord;
+
+/// The parts of a day's solution.
+#[derive(Clone, Copy, Debug)]
+pub struct Solution {
+    /// [`None`] for solutions in the unscoped layout (e.g. `src/bin/05.rs`), which belong to the configured year.
+    pub year: Option<Year>,
+    pub day: Day,
+    pub parts: &'static [(Part, PartFn)],
+}
+
+impl Solution {
+    pub fn puzzle(&self) -> PuzzleId {
+        PuzzleId::new(self.year.unwrap_or_else(Year::configured), self.day)
+    }
+
+    /// Reads the day's input and runs every part on it.
+    ///
+    /// Parts that the puzzle does not have (see [`PuzzleId::parts`]) are skipped.
+    /// Every part runs on its own thread, so a panic or exceeded timeout is recorded as a [`Failure`]
+    /// instead of taking down the whole process. Remaining parts of a day are skipped after a panic or timeout.
+    pub fn run(
+        &self,
+        bench: Option<BenchConfig>,
+        timeouts: &Timeouts,
+    ) -> Result<Vec<PartRecord>, io::Error> {
+        let puzzle = self.puzzle();
+        let input: Arc<str> = fs::read_to_string(puzzle.data_file("inputs", "txt"))?.into();
+        let mut records = vec![];
+
+        for &(part, func) in self.parts.iter().filter(|(part, _)| puzzle.has_part(*part)) {
+            let record = run_guarded(
+                puzzle,
+                part,
+                func,
+                &input,
+                bench,
+                timeouts.get(self.day, part),
+            )?;
+            let is_fatal = record.failure.is_some_and(Failure::is_fatal);
+            records.push(record);
+
+            if is_fatal {
+                break;
+            }
+        }
+
+        Ok(records)
+    }
+}
+
+fn run_guarded(
+    puzzle: PuzzleId,
+    part: Part,
+    func: PartFn,
+    input: &Arc<str>,
+    bench: Option<BenchConfig>,
+    timeout: Option<Duration>,
+) -> Result<PartRecord, io::Error> {
+    let (sender, receiver) = mpsc::channel();
+    let input = Arc::clone(input);
+    let day = puzzle.day;
+    let timer = Instant::now();
+
+    // NOTE: a timed out thread can not be stopped, it is detached and keeps running until the process exits.
+    thread::Builder::new()
+        .name(format!("day {day} part {part}"))
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            let result = panic::catch_unwind(|| func(&input, puzzle, bench));
+            let _ = sender.send(result.map_err(|e| panic_message(&*e)));
+        })?;
+
+    let result = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout),
+        None => receiver
+            .recv()
+            .map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+    };
+
+    Ok(match result {
+        Ok(Ok(record)) => record,
+        Ok(Err(message)) => {
+            PartRecord::failed(day, part, Failure::Panicked, message, timer.elapsed())
+        }
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            PartRecord::failed(day, part, Failure::TimedOut, None, timer.elapsed())
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            PartRecord::failed(day, part, Failure::Panicked, None, timer.elapsed())
+        }
+    })
+}
+
+/// Extracts the message passed to `panic!`, if any.
+fn panic_message(payload: &(dyn Any + Send)) -> Option<String> {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+}
+
+/// Finds the solution of a puzzle, if it is registered.
+pub fn find(solutions: &[Solution], puzzle: PuzzleId) -> Option<&Solution> {
+    solutions
+        .iter()
+        .find(|solution| solution.puzzle() == puzzle)
+}
@@ -1,49 +1,300 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io,
+    sync::{
+        atomic::{self, AtomicUsize},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    input::InputSource,
+    record::{OutputFormat, PartRecord},
+    registry::{self, Executor, Solution},
+    runner::{self, BenchConfig},
+    timeout::Timeouts,
+    Day, Part, PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// The outcome of running the solution of a single day.
+struct DayRun {
+    records: Vec<PartRecord>,
+    /// Buffered output, printed in day order once the day is finished.
+    output: String,
+    elapsed: Duration,
+}
+
+/// The result of [`run_multi`].
+pub struct Summary {
+    /// Timings of the solved parts, only present for timed runs.
+    pub timings: Option<Timings>,
+    /// Records of the parts that panicked, timed out, returned an error or produced a wrong answer.
+    pub failures: Vec<PartRecord>,
+}
+
+impl Summary {
+    pub fn has_failures(&self) -> bool {
+        !self.failures.is_empty()
+    }
+}
+
+/// Runs the solutions of multiple days of a year, benching them if a [`BenchConfig`] is passed.
+///
+/// Up to `jobs` days are run concurrently. Timed runs are always serial to avoid noise.
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    executor: Executor,
+    bench: Option<BenchConfig>,
+    format: OutputFormat,
+    jobs: usize,
+    timeouts: &Timeouts,
+) -> Summary {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut failures: Vec<PartRecord> = vec![];
 
-    let mut need_space = false;
+    let jobs = if bench.is_some() && jobs > 1 {
+        eprintln!("Ignoring `--jobs` for timed runs.");
+        1
+    } else {
+        jobs.max(1)
+    };
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let days: Vec<Day> = all_days(year)
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
+        .collect();
+
+    let mut need_space = false;
+    let mut summed_time = Duration::ZERO;
+    let timer = Instant::now();
+
+    run_ordered(
+        &days,
+        jobs,
+        |day| run_day(PuzzleId::new(year, day), executor, bench, format, timeouts),
+        |day, day_run| {
+            if format == OutputFormat::Human && need_space {
                 println!();
             }
             need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+            print!("{}", day_run.output);
+            summed_time += day_run.elapsed;
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
-
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+            if !day_run.records.is_empty() && bench.is_some() {
+                timings.push(timing_from_records(&day_run.records, day));
             }
+
+            failures.extend(day_run.records.into_iter().filter(PartRecord::is_failure));
+        },
+    );
+
+    if format == OutputFormat::Human && !failures.is_empty() {
+        println!("\n{ANSI_BOLD}Failures:{ANSI_RESET}");
+        failures.iter().for_each(|record| {
+            print!("Day {} {}", record.day, runner::format_record(record));
         });
+        println!("{}", summarize_failures(&failures));
+    }
 
-    if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
+    if format == OutputFormat::Human && jobs > 1 {
+        let wall_millis = timer.elapsed().as_secs_f64() * 1000_f64;
+        let summed_millis = summed_time.as_secs_f64() * 1000_f64;
         println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            "\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{wall_millis:.2}ms{ANSI_RESET} wall-clock, \
+            {ANSI_ITALIC}{summed_millis:.2}ms{ANSI_RESET} summed over days ({jobs} jobs)"
         );
-        Some(timings)
-    } else {
-        None
     }
+
+    let timings = bench.map(|_| {
+        let timings = Timings { data: timings };
+        if format == OutputFormat::Human {
+            let total_millis = timings.total_millis();
+            println!(
+                "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
+        timings
+    });
+
+    Summary { timings, failures }
+}
+
+/// Runs `run` for every day on up to `jobs` threads and passes the results to `emit` in the order of `days`.
+fn run_ordered<T: Send>(
+    days: &[Day],
+    jobs: usize,
+    run: impl Fn(Day) -> T + Sync,
+    mut emit: impl FnMut(Day, T),
+) {
+    if jobs <= 1 {
+        days.iter().for_each(|&day| emit(day, run(day)));
+        return;
+    }
+
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let sender = sender.clone();
+            let (next, run) = (&next, &run);
+
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, atomic::Ordering::Relaxed);
+                let Some(&day) = days.get(i) else {
+                    break;
+                };
+                if sender.send((i, run(day))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        // buffer results that finished early until all previous days are emitted.
+        let mut pending = BTreeMap::new();
+        let mut next_emit = 0;
+        for (i, result) in receiver {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&next_emit) {
+                emit(days[next_emit], result);
+                next_emit += 1;
+            }
+        }
+    });
+}
+
+fn run_day(
+    puzzle: PuzzleId,
+    executor: Executor,
+    bench: Option<BenchConfig>,
+    format: OutputFormat,
+    timeouts: &Timeouts,
+) -> DayRun {
+    let timer = Instant::now();
+    let mut output = String::new();
+
+    if format == OutputFormat::Human {
+        output.push_str(&format!(
+            "{ANSI_BOLD}Day {}{ANSI_RESET}\n------\n",
+            puzzle.day
+        ));
+    }
+
+    let records = match executor {
+        Executor::InProcess(solutions) => run_in_process(solutions, puzzle, bench, timeouts),
+        Executor::Isolated { is_release } => child_commands::run_solution(
+            puzzle,
+            &InputSource::Puzzle,
+            &[],
+            bench,
+            is_release,
+            format,
+            timeouts,
+            &mut output,
+        )
+        .unwrap(),
+    };
+
+    match format {
+        OutputFormat::Human if records.is_empty() => output.push_str("Not solved.\n"),
+        OutputFormat::Human => records
+            .iter()
+            .for_each(|record| output.push_str(&runner::format_record(record))),
+        OutputFormat::Json => records.iter().for_each(|record| {
+            output.push_str(&record.to_json_line());
+            output.push('\n');
+        }),
+    }
+
+    DayRun {
+        records,
+        output,
+        elapsed: timer.elapsed(),
+    }
+}
+
+/// Run the registered solution of a puzzle.
+fn run_in_process(
+    solutions: &[Solution],
+    puzzle: PuzzleId,
+    bench: Option<BenchConfig>,
+    timeouts: &Timeouts,
+) -> Vec<PartRecord> {
+    let Some(solution) = registry::find(solutions, puzzle) else {
+        return vec![];
+    };
+
+    match solution.run(bench, timeouts) {
+        Ok(records) => records,
+        Err(e) => {
+            eprintln!("Could not read input file of {puzzle}: {e}");
+            vec![]
+        }
+    }
+}
+
+/// Counts the failed parts by kind, e.g. `2 parts failed: 1 wrong answer, 1 returned an error`.
+fn summarize_failures(failures: &[PartRecord]) -> String {
+    let mut kinds: Vec<(String, usize)> = vec![];
+    for record in failures {
+        let kind = record
+            .failure
+            .map_or_else(|| "wrong answer".into(), |failure| failure.to_string());
+        match kinds.iter_mut().find(|(k, _)| *k == kind) {
+            Some((_, count)) => *count += 1,
+            None => kinds.push((kind, 1)),
+        }
+    }
+
+    let kinds: Vec<String> = kinds
+        .iter()
+        .map(|(kind, count)| format!("{count} {kind}"))
+        .collect();
+    let parts = if failures.len() == 1 { "part" } else { "parts" };
+    format!("{} {parts} failed: {}", failures.len(), kinds.join(", "))
+}
+
+pub fn timing_from_records(records: &[PartRecord], day: Day) -> Timing {
+    let mut timings = Timing {
+        day,
+        part_1: None,
+        part_2: None,
+        part_1_stats: None,
+        part_2_stats: None,
+        total_nanos: 0_f64,
+    };
+
+    records
+        .iter()
+        .filter(|record| record.answer.is_some())
+        .for_each(|record| {
+            let timing_str = format!("{:.1?}", record.duration());
+
+            match record.part {
+                Part::One => {
+                    timings.part_1 = Some(timing_str);
+                    timings.part_1_stats.clone_from(&record.stats);
+                }
+                Part::Two => {
+                    timings.part_2 = Some(timing_str);
+                    timings.part_2_stats.clone_from(&record.stats);
+                }
+            }
+
+            timings.total_nanos += record.time_nanos;
+        });
+
+    timings
 }
 
 #[allow(dead_code)]
@@ -59,45 +310,87 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// Every solution also lives in an isolated binary.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting the records they emit.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::Error;
+    use crate::template::{
+        input::InputSource,
+        record::{Failure, OutputFormat, PartRecord},
+        runner::BenchConfig,
+        timeout::Timeouts,
+        Part, PuzzleId,
+    };
     use std::{
         io::{BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
+        sync::mpsc::{self, RecvTimeoutError},
         thread,
+        time::Instant,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given puzzle on `input`, passing `child_args`, and collect the records of its parts.
+    /// Anything else the solution prints to stdout is appended to `output` (or forwarded to stderr for JSON output).
+    ///
+    /// A child that exceeds the timeout of a part is killed. Both timeouts and crashes are recorded as a failure of the
+    /// part that was running at the time.
+    #[allow(clippy::too_many_arguments)]
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        input: &InputSource,
+        child_args: &[String],
+        bench: Option<BenchConfig>,
+        is_release: bool,
+        format: OutputFormat,
+        timeouts: &Timeouts,
+        output: &mut String,
+    ) -> Result<Vec<PartRecord>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
+        if !Path::new(&puzzle.bin_path()).exists() {
+            return Ok(vec![]);
+        }
+
+        // mirror the in-process executor instead of letting the child panic.
+        if input.path(puzzle).is_some_and(|path| !path.exists()) {
+            eprintln!(
+                "Could not read input file of {puzzle}: {} not found",
+                input.describe(puzzle)
+            );
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let day = puzzle.day;
+        let bin_name = puzzle.bin_name();
+        let mut args = vec!["run", "--quiet", "--bin", &bin_name];
 
         if is_release {
             args.push("--release");
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
+        // compile ahead of time, so compilation does not count towards the timeout of the first part.
+        if !timeouts.is_empty() {
+            Command::new("cargo")
+                .arg("build")
+                .args(&args[1..])
+                .status()?;
+        }
+
+        // children always report in JSON, the output is formatted here.
+        args.extend(["--", "--format", "json"]);
+
+        let bench_args = bench.map(BenchConfig::to_args).unwrap_or_default();
+        if bench.is_some() {
+            // mirror `--time` flag and bench configuration to child invocations.
             args.push("--time");
+            args.extend(bench_args.iter().map(String::as_str));
         }
 
+        let input_args = input.to_args();
+        args.extend(input_args.iter().chain(child_args).map(String::as_str));
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward stderr while grabbing the records from stdout.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
@@ -108,150 +401,225 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let stderr_thread = thread::spawn(move || {
+            // keep the message of a panic, it is printed on the line after "thread '...' panicked at ...".
+            let mut panic_message = None;
+            let mut is_panic_message = false;
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+            stderr.lines().map_while(Result::ok).for_each(|line| {
+                eprintln!("{line}");
+                if is_panic_message {
+                    panic_message = Some(line.clone());
+                }
+                is_panic_message = line.contains(" panicked at ");
             });
+
+            panic_message
+        });
+
+        // read stdout on a separate thread, so we can stop waiting for a part that exceeds its timeout.
+        let (sender, receiver) = mpsc::channel();
+        let stdout_thread = thread::spawn(move || {
+            for line in stdout.lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let mut records: Vec<PartRecord> = vec![];
+        let mut timed_out = false;
+        let mut part_timer = Instant::now();
+
+        loop {
+            // solution binaries run their parts in order, the next record belongs to the part after the last one.
+            let part = next_part(puzzle, &records);
+
+            let line = match part.and_then(|part| timeouts.get(day, part)) {
+                Some(timeout) => {
+                    receiver.recv_timeout(timeout.saturating_sub(part_timer.elapsed()))
+                }
+                None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+
+            match line {
+                Ok(line) => match (PartRecord::from_json_line(&line), format) {
+                    (Ok(record), _) => {
+                        records.push(record);
+                        part_timer = Instant::now();
+                    }
+                    (Err(_), OutputFormat::Human) => {
+                        output.push_str(&line);
+                        output.push('\n');
+                    }
+                    // keep JSON output parseable by moving anything else the solution prints to stderr.
+                    (Err(_), OutputFormat::Json) => eprintln!("{line}"),
+                },
+                Err(RecvTimeoutError::Timeout) => {
+                    // NOTE: `cargo run` replaces itself with the solution binary on unix, so this stops the solution.
+                    cmd.kill()?;
+                    timed_out = true;
+                    break;
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let status = cmd.wait()?;
+        stdout_thread.join().unwrap();
+        let panic_message = stderr_thread.join().unwrap();
+
+        match next_part(puzzle, &records) {
+            Some(part) if timed_out => records.push(PartRecord::failed(
+                day,
+                part,
+                Failure::TimedOut,
+                None,
+                part_timer.elapsed(),
+            )),
+            Some(part) if !status.success() => records.push(PartRecord::failed(
+                day,
+                part,
+                Failure::Panicked,
+                panic_message.or_else(|| Some(format!("solution exited with {status}"))),
+                part_timer.elapsed(),
+            )),
+            // every part has a result already, e.g. submitting an answer failed afterwards.
+            None if !status.success() => {
+                eprintln!("{puzzle} exited with {status} after all of its parts finished.");
+            }
+            _ => {}
+        }
 
-        Ok(output)
+        Ok(records)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
-
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+    /// The part that runs after the recorded ones, [`None`] if every part of `puzzle` has a record.
+    pub(super) fn next_part(puzzle: PuzzleId, records: &[PartRecord]) -> Option<Part> {
+        match records.last() {
+            None => puzzle.parts().first().copied(),
+            Some(record) => record.part.next().filter(|&part| puzzle.has_part(part)),
+        }
+    }
+}
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+/// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
+#[cfg(feature = "test_lib")]
+macro_rules! assert_approx_eq {
+    ($a:expr, $b:expr) => {{
+        let (a, b) = (&$a, &$b);
+        assert!(
+            (*a - *b).abs() < 1.0e-6,
+            "{} is not approximately equal to {}",
+            *a,
+            *b
+        );
+    }};
+}
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{child_commands::next_part, summarize_failures, timing_from_records};
 
-                timings.total_nanos += nanos;
-            });
+    use crate::{
+        day,
+        template::{
+            record::{Failure, PartRecord},
+            Part, PuzzleId, Year,
+        },
+    };
 
-        timings
+    fn record(part: Part, answer: Option<&str>, time_nanos: f64, samples: u128) -> PartRecord {
+        PartRecord {
+            day: day!(1),
+            part,
+            answer: answer.map(Into::into),
+            time_nanos,
+            samples,
+            stats: None,
+            expected: None,
+            failure: None,
+            error: None,
+        }
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    #[test]
+    fn collects_execution_times() {
+        let res = timing_from_records(
+            &[
+                record(Part::One, Some("0"), 74.0, 100000),
+                record(Part::Two, Some("10"), 74_130_000.0, 99999),
+            ],
+            day!(1),
+        );
+        assert_approx_eq!(res.total_nanos, 74130074_f64);
+        assert_eq!(res.part_1.unwrap(), "74.0ns");
+        assert_eq!(res.part_2.unwrap(), "74.1ms");
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    #[test]
+    fn collects_multiline_answers() {
+        let res = timing_from_records(
+            &[
+                record(Part::One, Some("@ @ @ ( ) ms\n(1s @ 2 samples)"), 2e9, 5),
+                record(Part::Two, Some("10s"), 1e8, 1),
+            ],
+            day!(1),
+        );
+        assert_approx_eq!(res.total_nanos, 2100000000_f64);
+        assert_eq!(res.part_1.unwrap(), "2.0s");
+        assert_eq!(res.part_2.unwrap(), "100.0ms");
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    #[test]
+    fn collects_missing_parts() {
+        let res = timing_from_records(
+            &[
+                record(Part::One, None, 12.0, 1),
+                record(Part::Two, None, 13.0, 1),
+            ],
+            day!(1),
+        );
+        assert_approx_eq!(res.total_nanos, 0_f64);
+        assert_eq!(res.part_1.is_none(), true);
+        assert_eq!(res.part_2.is_none(), true);
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
+    #[test]
+    fn finds_the_next_part() {
+        let year = Year::new(2024).unwrap();
+        let puzzle = PuzzleId::new(year, day!(1));
+        let one = record(Part::One, Some("1"), 1.0, 1);
+        let two = record(Part::Two, Some("2"), 1.0, 1);
+
+        assert_eq!(next_part(puzzle, &[]), Some(Part::One));
+        assert_eq!(next_part(puzzle, &[one.clone()]), Some(Part::Two));
+        assert_eq!(next_part(puzzle, &[one.clone(), two]), None);
+        assert_eq!(next_part(PuzzleId::new(year, day!(25)), &[one]), None);
+    }
 
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+    #[test]
+    fn summarizes_failure_kinds() {
+        let wrong = PartRecord {
+            expected: Some("1".into()),
+            ..record(Part::One, Some("0"), 1.0, 1)
+        };
+        let errored = PartRecord {
+            failure: Some(Failure::Errored),
+            ..record(Part::Two, None, 1.0, 1)
+        };
+        assert_eq!(
+            summarize_failures(&[wrong.clone(), errored, wrong]),
+            "3 parts failed: 2 wrong answer, 1 returned an error"
+        );
 
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+        let timed_out = PartRecord {
+            failure: Some(Failure::TimedOut),
+            ..record(Part::One, None, 1.0, 1)
+        };
+        assert_eq!(
+            summarize_failures(&[timed_out]),
+            "1 part failed: 1 timed out"
+        );
     }
 }
@@ -1,35 +1,291 @@
 /// Encapsulates code that interacts with solution functions.
+use std::error::Error;
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{self, stdout, Write};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, process};
 
+use crate::template::answers::{Answers, Check};
+use crate::template::aoc_client::{Backend, Client, Verdict};
+use crate::template::error::report;
+use crate::template::input::InputSource;
+use crate::template::logging;
+use crate::template::record::{Failure, OutputFormat, PartRecord};
+use crate::template::stats::Stats;
+use crate::template::submissions::{self, SubmissionEntry, SubmissionLog};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, Part, PuzzleId, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Configuration of the benchmark performed for `--time` runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Iterations that are executed before taking samples.
+    pub warmup: u32,
+    /// Wall-clock time to spend on taking samples.
+    pub target_time: Duration,
+}
+
+impl BenchConfig {
+    /// Lower and upper bounds of samples taken, regardless of the target time.
+    const MIN_SAMPLES: u128 = 10;
+    const MAX_SAMPLES: u128 = 10000;
+
+    /// Reads the `--warmup` and `--target-time` (in ms) arguments passed to a solution binary.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        let value = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .and_then(|i| args.get(i + 1))
+                .and_then(|v| v.parse::<u64>().ok())
+        };
+
+        let default = Self::default();
+        Self {
+            warmup: value("--warmup")
+                .map_or(default.warmup, |w| u32::try_from(w).unwrap_or(u32::MAX)),
+            target_time: value("--target-time").map_or(default.target_time, Duration::from_millis),
+        }
+    }
+
+    /// Arguments that mirror this configuration to a child invocation.
+    pub fn to_args(self) -> Vec<String> {
+        vec![
+            "--warmup".into(),
+            self.warmup.to_string(),
+            "--target-time".into(),
+            self.target_time.as_millis().to_string(),
+        ]
+    }
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            target_time: Duration::from_secs(1),
+        }
+    }
+}
+
+/// The return value of a solution part: either an [`Option`], where [`None`] means "not solved",
+/// or a [`Result`], where an error means that the part failed (e.g. because of malformed input).
+pub trait PartResult {
+    type Answer: Display;
+
+    /// The answer of the part, if any, or the error it returned.
+    fn answer(&self) -> Result<Option<&Self::Answer>, &(dyn Error + 'static)>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    type Answer = T;
+
+    fn answer(&self) -> Result<Option<&T>, &(dyn Error + 'static)> {
+        Ok(self.as_ref())
+    }
+}
+
+impl<T: Display, E: Error + 'static> PartResult for Result<T, E> {
+    type Answer = T;
+
+    fn answer(&self) -> Result<Option<&T>, &(dyn Error + 'static)> {
+        match self {
+            Ok(answer) => Ok(Some(answer)),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+pub fn run_part<I: Copy, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: PuzzleId,
+    part: Part,
+) {
+    let format = OutputFormat::from_args();
+    let bench = is_timed().then(BenchConfig::from_args);
     let part_str = format!("Part {part}");
+    // answers for other inputs can not be checked, accepted or submitted
+    let is_puzzle_input = InputSource::from_args() == InputSource::Puzzle;
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, duration, stats) = run_timed(func, input, bench, |result: &R| {
+        if format == OutputFormat::Human {
+            match result.answer() {
+                Ok(answer) => print_result(&answer, &part_str, ""),
+                Err(_) => print!("{part_str}: ✖"),
+            }
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+            if bench.is_some() {
+                print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+                let _ = stdout().flush();
+            }
+        }
+    });
+    let answer = result.answer();
+
+    // store before printing, so the printed check reflects the accepted answer.
+    let accepted = answer
+        .ok()
+        .flatten()
+        .filter(|_| is_puzzle_input)
+        .and_then(|answer| accept_result(answer, puzzle, part));
+
+    match format {
+        OutputFormat::Human => match answer {
+            Ok(answer) => {
+                let samples = stats.as_ref().map_or(1, |stats| stats.samples);
+                let check = answer
+                    .filter(|_| is_puzzle_input)
+                    .map_or(String::new(), |answer| {
+                        let expected = Answers::read_from_file(puzzle.year);
+                        let check = Check::new(&answer.to_string(), expected.get(puzzle.day, part));
+                        format!(" {check}")
+                    });
+
+                print_result(
+                    &answer,
+                    &part_str,
+                    &format!("{}{check}", format_duration(&duration, samples)),
+                );
+                if let Some(stats) = &stats {
+                    print_stats(stats);
+                }
+            }
+            Err(error) => {
+                print!("\r");
+                print!("{}", format_error(&part_str, &report(error)));
+            }
+        },
+        OutputFormat::Json => {
+            let mut record = to_record(puzzle, part, answer, duration, stats);
+            if !is_puzzle_input {
+                record.expected = None;
+            }
+            println!("{}", record.to_json_line());
+        }
+    }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    match accepted {
+        Some(Ok(())) if format == OutputFormat::Human => {
+            println!("Accepted answer of part {part}.")
+        }
+        Some(Err(e)) => eprintln!("Failed to store the answer of part {part}: {e}"),
+        _ => {}
     }
+
+    if let (Ok(Some(answer)), true) = (answer, is_puzzle_input) {
+        submit_result(answer, puzzle, part);
+    }
+}
+
+/// Runs a solution part without printing anything, benching it if a [`BenchConfig`] is passed.
+/// This is used by the in-process [`registry`](crate::template::registry).
+pub fn measure_part<I: Copy, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: PuzzleId,
+    part: Part,
+    bench: Option<BenchConfig>,
+) -> PartRecord {
+    let (result, duration, stats) = run_timed(func, input, bench, |_| {});
+    to_record(puzzle, part, result.answer(), duration, stats)
+}
+
+fn to_record<T: Display>(
+    puzzle: PuzzleId,
+    part: Part,
+    answer: Result<Option<&T>, &(dyn Error + 'static)>,
+    duration: Duration,
+    stats: Option<Stats>,
+) -> PartRecord {
+    let (answer, failure, error) = match answer {
+        Ok(answer) => (answer.map(ToString::to_string), None, None),
+        Err(error) => (None, Some(Failure::Errored), Some(report(error))),
+    };
+
+    PartRecord {
+        day: puzzle.day,
+        part,
+        answer,
+        #[allow(clippy::cast_precision_loss)]
+        time_nanos: duration.as_nanos() as f64,
+        samples: stats.as_ref().map_or(1, |stats| stats.samples),
+        stats,
+        expected: Answers::read_from_file(puzzle.year)
+            .get(puzzle.day, part)
+            .map(Into::into),
+        failure,
+        error,
+    }
+}
+
+/// Formats a record of a part that was run by `run_multi` in the human readable format.
+pub(crate) fn format_record(record: &PartRecord) -> String {
+    let part = format!("Part {}", record.part);
+    let duration_str = format_duration(&record.duration(), record.samples);
+
+    let check = record
+        .check()
+        .map_or(String::new(), |check| format!(" {check}"));
+
+    let mut output = match (&record.answer, record.failure) {
+        (_, Some(Failure::TimedOut)) => {
+            format!(
+                "{part}: ✖ {ANSI_BOLD}timed out{ANSI_RESET} after {:.1?}\n",
+                record.duration()
+            )
+        }
+        (_, Some(Failure::Panicked)) => match &record.error {
+            Some(error) => format!("{part}: ✖ {ANSI_BOLD}panicked{ANSI_RESET}: {error}\n"),
+            None => format!("{part}: ✖ {ANSI_BOLD}panicked{ANSI_RESET}\n"),
+        },
+        (_, Some(Failure::Errored)) => {
+            format_error(&part, record.error.as_deref().unwrap_or("unknown error"))
+        }
+        (Some(answer), None) if answer.contains('\n') => {
+            format!("{part}: ▼ {duration_str}{check}\n{answer}\n")
+        }
+        (Some(answer), None) => {
+            format!("{part}: {ANSI_BOLD}{answer}{ANSI_RESET}{duration_str}{check}\n")
+        }
+        (None, None) => format!("{part}: ✖\n"),
+    };
+
+    if let Some(stats) = &record.stats {
+        output.push_str(&format_stats(stats));
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Formats the report of an error returned by a part, indenting the lines after the first one.
+fn format_error(part: &str, report: &str) -> String {
+    let mut lines = report.lines();
+    let mut output = format!(
+        "{part}: ✖ {ANSI_BOLD}error{ANSI_RESET}: {}\n",
+        lines.next().unwrap_or_default()
+    );
+    for line in lines {
+        output.push_str(&format!("        {line}\n"));
+    }
+    output
+}
+
+fn is_timed() -> bool {
+    env::args().any(|x| x == "--time")
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched (see [`BenchConfig`]) and the mean execution time is returned alongside the statistics.
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
+    bench_config: Option<BenchConfig>,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, Option<Stats>) {
     let timer = Instant::now();
     let result = {
         #[cfg(feature = "dhat-heap")]
@@ -41,45 +297,41 @@ fn run_timed<I: Copy, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
+    let Some(bench_config) = bench_config else {
+        return (result, base_time, None);
     };
 
-    (result, run.0, run.1)
-}
+    let stats = bench(func, input, &bench_config);
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let mean = Duration::from_nanos(stats.mean as u64);
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+    (result, mean, Some(stats))
+}
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+/// Takes samples of `func`, its logs are muted.
+fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, config: &BenchConfig) -> Stats {
+    logging::muted(|| sample(func, input, config))
+}
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+fn sample<I: Copy, T>(func: impl Fn(I) -> T, input: I, config: &BenchConfig) -> Stats {
+    for _ in 0..config.warmup {
+        black_box(func(black_box(input)));
+    }
 
     let mut timers: Vec<Duration> = vec![];
+    let bench_timer = Instant::now();
 
-    for _ in 0..bench_iterations {
+    // sample until the target time is spent, but within the sample bounds.
+    while (timers.len() as u128) < BenchConfig::MAX_SAMPLES
+        && ((timers.len() as u128) < BenchConfig::MIN_SAMPLES
+            || bench_timer.elapsed() < config.target_time)
+    {
         let timer = Instant::now();
         black_box(func(black_box(input)));
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
-
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    Stats::from_samples(&timers).expect("at least one sample is taken")
 }
 
 fn format_duration(duration: &Duration, samples: u128) -> String {
@@ -90,6 +342,27 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
+fn print_stats(stats: &Stats) {
+    println!("{}", format_stats(stats));
+}
+
+fn format_stats(stats: &Stats) -> String {
+    let nanos = |x: f64| {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Duration::from_nanos(x as u64)
+    };
+
+    format!(
+        "        {ANSI_ITALIC}median {:.1?}, min {:.1?}, max {:.1?}, σ {:.1?}, p95 {:.1?}, {} outliers{ANSI_RESET}",
+        nanos(stats.median),
+        nanos(stats.min),
+        nanos(stats.max),
+        nanos(stats.std_dev),
+        nanos(stats.p95),
+        stats.outliers.total()
+    )
+}
+
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
@@ -125,18 +398,64 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+/// Store the result as the accepted answer if `--accept` is passed to `solve`.
+fn accept_result<T: Display>(result: &T, puzzle: PuzzleId, part: Part) -> Option<io::Result<()>> {
+    if !env::args().any(|x| x == "--accept") {
+        return None;
+    }
+
+    let mut answers = Answers::read_from_file(puzzle.year);
+    answers.set(puzzle.day, part, result.to_string());
+    Some(answers.store_file(puzzle.year))
+}
+
+/// Submits an answer unless the submission log shows that it is pointless, and records the verdict.
+fn submit_native(answer: &str, puzzle: PuzzleId, part: Part) {
+    let mut log = SubmissionLog::read_from_file(puzzle.year);
+
+    if let Err(refusal) = log.check(puzzle.day, part, answer, submissions::now()) {
+        eprintln!("Not submitting part {part}: {refusal}");
+        return;
+    }
+
+    println!("Submitting result...");
+    let submission = match Client::from_env().and_then(|client| client.submit(puzzle, part, answer))
+    {
+        Ok(submission) => submission,
+        Err(e) => {
+            eprintln!("failed to submit result: {e}");
+            process::exit(1);
+        }
+    };
+
+    println!("{}", submission.message);
+
+    log.append(SubmissionEntry::new(
+        puzzle.day,
+        part,
+        answer,
+        &submission,
+        submissions::now(),
+    ));
+    if let Err(e) = log.store_file(puzzle.year) {
+        eprintln!("Failed to store the submission log: {e}");
+    }
+
+    if submission.verdict == Verdict::Correct {
+        let mut answers = Answers::read_from_file(puzzle.year);
+        answers.set(puzzle.day, part, answer.to_string());
+        if let Err(e) = answers.store_file(puzzle.year) {
+            eprintln!("Failed to store the answer of part {part}: {e}");
+        }
+    }
+}
+
+/// Parse the arguments passed to `solve` and try to submit one part of the solution if `--submit <part>` is passed.
+fn submit_result<T: Display>(result: T, puzzle: PuzzleId, part: Part) {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
-        return None;
+        return;
     }
 
     if args.len() < 3 {
@@ -146,20 +465,25 @@ fn submit_result<T: Display>(
 
     let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
+    let Ok(part_submit) = args[part_index].parse::<Part>() else {
         eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
         process::exit(1);
     };
 
     if part_submit != part {
-        return None;
+        return;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+    match Backend::from_env() {
+        Backend::Native => submit_native(&result.to_string(), puzzle, part),
+        Backend::AocCli => {
+            if aoc_cli::check().is_err() {
+                eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+                process::exit(1);
+            }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+            println!("Submitting result via aoc-cli...");
+            let _ = aoc_cli::submit(puzzle, part, &result.to_string());
+        }
+    }
 }
@@ -0,0 +1,262 @@
+/// Summary statistics over the samples taken while benching a solution part.
+use std::{collections::HashMap, time::Duration};
+
+use tinyjson::JsonValue;
+
+/// Number of samples classified as outliers by Tukey's fences.
+///
+/// Mild outliers lie more than 1.5, severe outliers more than 3 interquartile ranges outside of the quartiles.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Outliers {
+    pub low_severe: usize,
+    pub low_mild: usize,
+    pub high_mild: usize,
+    pub high_severe: usize,
+}
+
+impl Outliers {
+    pub fn total(&self) -> usize {
+        self.low_severe + self.low_mild + self.high_mild + self.high_severe
+    }
+}
+
+/// Statistics of a benchmark run. All durations are in nanoseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub samples: u128,
+    pub mean: f64,
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+    pub std_dev: f64,
+    pub p5: f64,
+    pub p25: f64,
+    pub p75: f64,
+    pub p95: f64,
+    pub p99: f64,
+    pub outliers: Outliers,
+}
+
+impl Stats {
+    /// Computes the statistics of a set of samples, returns [`None`] if there are none.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        #[allow(clippy::cast_precision_loss)]
+        let count = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / count;
+        let variance = if nanos.len() > 1 {
+            nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (count - 1.0)
+        } else {
+            0.0
+        };
+
+        let p25 = percentile(&nanos, 25.0);
+        let p75 = percentile(&nanos, 75.0);
+        let iqr = p75 - p25;
+
+        let mut outliers = Outliers::default();
+        for &x in &nanos {
+            if x < p25 - 3.0 * iqr {
+                outliers.low_severe += 1;
+            } else if x < p25 - 1.5 * iqr {
+                outliers.low_mild += 1;
+            } else if x > p75 + 3.0 * iqr {
+                outliers.high_severe += 1;
+            } else if x > p75 + 1.5 * iqr {
+                outliers.high_mild += 1;
+            }
+        }
+
+        Some(Self {
+            samples: samples.len() as u128,
+            mean,
+            median: percentile(&nanos, 50.0),
+            min: nanos[0],
+            max: nanos[nanos.len() - 1],
+            std_dev: variance.sqrt(),
+            p5: percentile(&nanos, 5.0),
+            p25,
+            p75,
+            p95: percentile(&nanos, 95.0),
+            p99: percentile(&nanos, 99.0),
+            outliers,
+        })
+    }
+}
+
+/// Linearly interpolated percentile of a sorted, non-empty slice.
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let rank = pct / 100.0 * (sorted.len() - 1) as f64;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    let weight = rank - rank.floor();
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * weight
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("max".into(), JsonValue::Number(value.max));
+        map.insert("std_dev".into(), JsonValue::Number(value.std_dev));
+        map.insert("p5".into(), JsonValue::Number(value.p5));
+        map.insert("p25".into(), JsonValue::Number(value.p25));
+        map.insert("p75".into(), JsonValue::Number(value.p75));
+        map.insert("p95".into(), JsonValue::Number(value.p95));
+        map.insert("p99".into(), JsonValue::Number(value.p99));
+
+        let mut outliers: HashMap<String, JsonValue> = HashMap::new();
+        #[allow(clippy::cast_precision_loss)]
+        {
+            let o = &value.outliers;
+            outliers.insert("low_severe".into(), JsonValue::Number(o.low_severe as f64));
+            outliers.insert("low_mild".into(), JsonValue::Number(o.low_mild as f64));
+            outliers.insert("high_mild".into(), JsonValue::Number(o.high_mild as f64));
+            outliers.insert(
+                "high_severe".into(),
+                JsonValue::Number(o.high_severe as f64),
+            );
+        }
+        map.insert("outliers".into(), JsonValue::Object(outliers));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        let outliers = json
+            .get("outliers")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("Expected stats.outliers to be a JSON object.")?;
+
+        let count = |key: &str| {
+            outliers
+                .get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|v| v as usize)
+                .ok_or(format!("Expected stats.outliers.{key} to be a number."))
+        };
+
+        Ok(Stats {
+            samples: number("samples")? as u128,
+            mean: number("mean")?,
+            median: number("median")?,
+            min: number("min")?,
+            max: number("max")?,
+            std_dev: number("std_dev")?,
+            p5: number("p5")?,
+            p25: number("p25")?,
+            p75: number("p75")?,
+            p95: number("p95")?,
+            p99: number("p99")?,
+            outliers: Outliers {
+                low_severe: count("low_severe")?,
+                low_mild: count("low_mild")?,
+                high_mild: count("high_mild")?,
+                high_severe: count("high_severe")?,
+            },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::Stats;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = Stats::from_samples(&nanos(&[42])).unwrap();
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.mean, 42.0);
+        assert_eq!(stats.median, 42.0);
+        assert_eq!(stats.p99, 42.0);
+        assert_eq!(stats.std_dev, 0.0);
+        assert_eq!(stats.outliers.total(), 0);
+    }
+
+    #[test]
+    fn computes_summary() {
+        let stats = Stats::from_samples(&nanos(&[5, 1, 4, 2, 3])).unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.mean, 3.0);
+        assert_eq!(stats.median, 3.0);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.max, 5.0);
+        assert_eq!(stats.p25, 2.0);
+        assert_eq!(stats.p75, 4.0);
+        assert!((stats.std_dev - 2.5_f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn interpolates_percentiles() {
+        let stats = Stats::from_samples(&nanos(&[10, 20])).unwrap();
+        assert_eq!(stats.median, 15.0);
+        assert_eq!(stats.p25, 12.5);
+        assert_eq!(stats.p95, 19.5);
+    }
+
+    #[test]
+    fn classifies_outliers() {
+        let stats = Stats::from_samples(&nanos(&[
+            100, 101, 102, 103, 104, 105, 106, 107, 118, 200, 1,
+        ]))
+        .unwrap();
+        assert_eq!(stats.outliers.high_mild, 1);
+        assert_eq!(stats.outliers.high_severe, 1);
+        assert_eq!(stats.outliers.low_severe, 1);
+        assert_eq!(stats.outliers.low_mild, 0);
+        assert_eq!(stats.outliers.total(), 3);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = Stats::from_samples(&nanos(&[1, 2, 3, 4, 100])).unwrap();
+        let json = JsonValue::from(&stats);
+        assert_eq!(Stats::try_from(&json).unwrap(), stats);
+    }
+}
@@ -0,0 +1,407 @@
+/// A local log of submitted answers, used to avoid pointless submissions.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::Error,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{
+    aoc_client::{Submission, Verdict},
+    data_path, Day, Part, Year,
+};
+
+static SUBMISSIONS_FILE_NAME: &str = "submissions.json";
+
+/// A submitted answer and the verdict of the server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubmissionEntry {
+    pub day: Day,
+    pub part: Part,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Seconds since the unix epoch until which the server does not accept answers for this day.
+    pub wait_until: Option<u64>,
+}
+
+impl SubmissionEntry {
+    pub fn new(
+        day: Day,
+        part: Part,
+        answer: &str,
+        submission: &Submission,
+        timestamp: u64,
+    ) -> Self {
+        Self {
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict: submission.verdict,
+            timestamp,
+            wait_until: submission.wait.map(|wait| timestamp + wait.as_secs()),
+        }
+    }
+}
+
+/// The reason an answer is not submitted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved {
+        answer: String,
+    },
+    KnownWrong {
+        verdict: Verdict,
+    },
+    /// A smaller or equal answer was too high.
+    AboveBound {
+        too_high: i128,
+    },
+    /// A larger or equal answer was too low.
+    BelowBound {
+        too_low: i128,
+    },
+    Cooldown {
+        seconds_left: u64,
+    },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved { answer } => {
+                write!(f, "this part is already solved with the answer {answer}.")
+            }
+            Refusal::KnownWrong { verdict } => {
+                write!(f, "this answer was already submitted and is {verdict}.")
+            }
+            Refusal::AboveBound { too_high } => {
+                write!(f, "the answer {too_high} was already too high.")
+            }
+            Refusal::BelowBound { too_low } => {
+                write!(f, "the answer {too_low} was already too low.")
+            }
+            Refusal::Cooldown { seconds_left } => {
+                write!(
+                    f,
+                    "the server asked to wait, try again in {seconds_left} seconds."
+                )
+            }
+        }
+    }
+}
+
+/// Every submitted answer, oldest first.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SubmissionLog {
+    pub entries: Vec<SubmissionEntry>,
+}
+
+impl SubmissionLog {
+    /// Dehydrate the log of a year to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let path = data_path(year, SUBMISSIONS_FILE_NAME);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the log of a year from a JSON file. If not present, returns an empty log.
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(data_path(year, SUBMISSIONS_FILE_NAME))
+            .map_err(|x| x.to_string())
+            .and_then(SubmissionLog::try_from)
+            .unwrap_or_default()
+    }
+
+    pub fn append(&mut self, entry: SubmissionEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Checks whether an answer is worth submitting at time `now` (seconds since the unix epoch).
+    pub fn check(&self, day: Day, part: Part, answer: &str, now: u64) -> Result<(), Refusal> {
+        let day_entries = || self.entries.iter().filter(move |e| e.day == day);
+        let part_entries = || day_entries().filter(move |e| e.part == part);
+
+        if let Some(solved) = part_entries().find(|e| e.verdict == Verdict::Correct) {
+            return Err(Refusal::AlreadySolved {
+                answer: solved.answer.clone(),
+            });
+        }
+
+        if let Some(known) = part_entries().find(|e| e.answer == answer && is_wrong(e.verdict)) {
+            return Err(Refusal::KnownWrong {
+                verdict: known.verdict,
+            });
+        }
+
+        if let Ok(value) = answer.trim().parse::<i128>() {
+            let numeric = |verdict: Verdict| {
+                part_entries()
+                    .filter(move |e| e.verdict == verdict)
+                    .filter_map(|e| e.answer.trim().parse::<i128>().ok())
+            };
+
+            if let Some(too_high) = numeric(Verdict::TooHigh).min().filter(|&h| value >= h) {
+                return Err(Refusal::AboveBound { too_high });
+            }
+
+            if let Some(too_low) = numeric(Verdict::TooLow).max().filter(|&l| value <= l) {
+                return Err(Refusal::BelowBound { too_low });
+            }
+        }
+
+        // the cooldown applies to both parts of a day.
+        if let Some(wait_until) = day_entries()
+            .filter_map(|e| e.wait_until)
+            .max()
+            .filter(|&w| w > now)
+        {
+            return Err(Refusal::Cooldown {
+                seconds_left: wait_until - now,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+fn is_wrong(verdict: Verdict) -> bool {
+    matches!(verdict, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+}
+
+/// Seconds since the unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&SubmissionLog> for JsonValue {
+    fn from(value: &SubmissionLog) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "entries".into(),
+            JsonValue::Array(value.entries.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for SubmissionLog {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_entries = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("entries")
+            .ok_or("expected JSON document to have key `entries`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.entries` to be an array.")?;
+
+        Ok(SubmissionLog {
+            entries: json_entries
+                .iter()
+                .map(SubmissionEntry::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&SubmissionEntry> for JsonValue {
+    fn from(value: &SubmissionEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "part".into(),
+            JsonValue::Number(value.part.into_inner().into()),
+        );
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.to_string()),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "wait_until".into(),
+            value
+                .wait_until
+                .map_or(JsonValue::Null, |w| JsonValue::Number(w as f64)),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for SubmissionEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .and_then(|part| Part::iter().find(|p| f64::from(p.into_inner()) == *part))
+            .ok_or("Expected submission.part to be either 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .and_then(|v| v.parse::<Verdict>().ok())
+            .ok_or("Expected submission.verdict to be a verdict.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        let wait_until = json
+            .get("wait_until")
+            .map(|v| if v.is_null() { None } else { v.get::<f64>() })
+            .ok_or("Expected submission.wait_until to be null or a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(SubmissionEntry {
+            day,
+            part,
+            answer: answer.clone(),
+            verdict,
+            timestamp: timestamp as u64,
+            wait_until: wait_until.map(|w| *w as u64),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{Refusal, SubmissionEntry, SubmissionLog};
+    use crate::{
+        day,
+        template::{aoc_client::Verdict, Day, Part},
+    };
+
+    fn entry(day: Day, part: Part, answer: &str, verdict: Verdict) -> SubmissionEntry {
+        SubmissionEntry {
+            day,
+            part,
+            answer: answer.into(),
+            verdict,
+            timestamp: 1000,
+            wait_until: None,
+        }
+    }
+
+    fn get_mock_log() -> SubmissionLog {
+        SubmissionLog {
+            entries: vec![
+                entry(day!(9), Part::One, "100", Verdict::TooHigh),
+                entry(day!(9), Part::One, "50", Verdict::TooHigh),
+                entry(day!(9), Part::One, "10", Verdict::TooLow),
+                entry(day!(9), Part::One, "abc", Verdict::Wrong),
+                entry(day!(9), Part::Two, "7", Verdict::Correct),
+            ],
+        }
+    }
+
+    #[test]
+    fn refuses_known_answers() {
+        let log = get_mock_log();
+
+        assert_eq!(
+            log.check(day!(9), Part::One, "abc", 2000),
+            Err(Refusal::KnownWrong {
+                verdict: Verdict::Wrong
+            })
+        );
+        assert_eq!(
+            log.check(day!(9), Part::Two, "8", 2000),
+            Err(Refusal::AlreadySolved { answer: "7".into() })
+        );
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let log = get_mock_log();
+
+        assert_eq!(
+            log.check(day!(9), Part::One, "60", 2000),
+            Err(Refusal::AboveBound { too_high: 50 })
+        );
+        assert_eq!(
+            log.check(day!(9), Part::One, "-3", 2000),
+            Err(Refusal::BelowBound { too_low: 10 })
+        );
+        assert_eq!(log.check(day!(9), Part::One, "11", 2000), Ok(()));
+        assert_eq!(log.check(day!(9), Part::One, "def", 2000), Ok(()));
+        assert_eq!(log.check(day!(8), Part::One, "60", 2000), Ok(()));
+    }
+
+    #[test]
+    fn respects_cooldowns() {
+        let mut log = get_mock_log();
+        log.append(SubmissionEntry {
+            wait_until: Some(1060),
+            ..entry(day!(9), Part::One, "12", Verdict::RateLimited)
+        });
+
+        assert_eq!(
+            log.check(day!(9), Part::One, "13", 1030),
+            Err(Refusal::Cooldown { seconds_left: 30 })
+        );
+        assert_eq!(log.check(day!(9), Part::One, "13", 1060), Ok(()));
+        // rate limited answers are not known to be wrong.
+        assert_eq!(log.check(day!(9), Part::One, "12", 1060), Ok(()));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut log = get_mock_log();
+        log.append(SubmissionEntry {
+            wait_until: Some(1060),
+            ..entry(day!(1), Part::Two, "1", Verdict::RateLimited)
+        });
+
+        let json = JsonValue::from(&log).stringify().unwrap();
+        assert_eq!(SubmissionLog::try_from(json).unwrap(), log);
+    }
+}
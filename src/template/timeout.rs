@@ -0,0 +1,156 @@
+/// Time limits for solution parts run by `cargo all`.
+use std::{error::Error, fmt::Display, str::FromStr, time::Duration};
+
+use crate::template::{Day, Part};
+
+/// Limits the execution time of all parts, the parts of a day, or a single part.
+///
+/// Parses from `SECS`, `DAY=SECS` or `DAY:PART=SECS`, e.g. `10`, `6=30` or `09:2=0.5`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeoutRule {
+    pub day: Option<Day>,
+    pub part: Option<Part>,
+    pub limit: Duration,
+}
+
+impl FromStr for TimeoutRule {
+    type Err = TimeoutRuleFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (target, secs) = s.rsplit_once('=').map_or((None, s), |(t, s)| (Some(t), s));
+
+        let limit = secs
+            .parse::<f64>()
+            .ok()
+            .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+            .filter(|limit| !limit.is_zero())
+            .ok_or(TimeoutRuleFromStrError)?;
+
+        let (day, part) = match target {
+            None => (None, None),
+            Some(target) => {
+                let (day, part) = target
+                    .split_once(':')
+                    .map_or((target, None), |(day, part)| (day, Some(part)));
+
+                let day = day.parse::<Day>().map_err(|_| TimeoutRuleFromStrError)?;
+                let part = part
+                    .map(|p| p.parse::<Part>().map_err(|_| TimeoutRuleFromStrError))
+                    .transpose()?;
+
+                (Some(day), part)
+            }
+        };
+
+        Ok(Self { day, part, limit })
+    }
+}
+
+/// An error which can be returned when parsing a [`TimeoutRule`].
+#[derive(Debug)]
+pub struct TimeoutRuleFromStrError;
+
+impl Error for TimeoutRuleFromStrError {}
+
+impl Display for TimeoutRuleFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a timeout in seconds, optionally scoped to a day or part: `SECS`, `DAY=SECS` or `DAY:PART=SECS`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A set of [`TimeoutRule`]s. Parts without a matching rule run without a time limit.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Timeouts {
+    rules: Vec<TimeoutRule>,
+}
+
+impl Timeouts {
+    pub fn new(rules: Vec<TimeoutRule>) -> Self {
+        Self { rules }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Returns the time limit of a part.
+    /// The most specific rule wins, and later rules win over earlier ones of the same specificity.
+    pub fn get(&self, day: Day, part: Part) -> Option<Duration> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.day.is_none_or(|d| d == day))
+            .filter(|rule| rule.part.is_none_or(|p| p == part))
+            .max_by_key(|rule| (rule.day.is_some(), rule.part.is_some()))
+            .map(|rule| rule.limit)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{TimeoutRule, Timeouts};
+    use crate::{day, template::Part};
+
+    fn rule(s: &str) -> TimeoutRule {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_rules() {
+        assert_eq!(
+            rule("10"),
+            TimeoutRule {
+                day: None,
+                part: None,
+                limit: Duration::from_secs(10)
+            }
+        );
+        assert_eq!(
+            rule("6=30"),
+            TimeoutRule {
+                day: Some(day!(6)),
+                part: None,
+                limit: Duration::from_secs(30)
+            }
+        );
+        assert_eq!(
+            rule("09:2=0.5"),
+            TimeoutRule {
+                day: Some(day!(9)),
+                part: Some(Part::Two),
+                limit: Duration::from_millis(500)
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_rules() {
+        for s in ["", "0", "-1", "ten", "26=1", "6:3=1", "6:=1", "=1"] {
+            assert!(s.parse::<TimeoutRule>().is_err(), "{s} should be rejected");
+        }
+    }
+
+    #[test]
+    fn prefers_specific_rules() {
+        let timeouts = Timeouts::new(vec![rule("6:2=3"), rule("6=2"), rule("1"), rule("5")]);
+
+        assert_eq!(
+            timeouts.get(day!(1), Part::One),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(
+            timeouts.get(day!(6), Part::One),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            timeouts.get(day!(6), Part::Two),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(Timeouts::default().get(day!(6), Part::Two), None);
+    }
+}
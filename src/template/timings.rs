@@ -1,9 +1,17 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs,
+    io::Error,
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{data_path, stats::Stats, Day, Part, PuzzleId, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
+static HISTORY_FILE_NAME: &str = "timings_history.json";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -11,9 +19,28 @@ pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub part_1_stats: Option<Stats>,
+    pub part_2_stats: Option<Stats>,
     pub total_nanos: f64,
 }
 
+impl Timing {
+    /// The formatted execution time of a part, if it was solved.
+    pub fn part(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part_1.as_deref(),
+            Part::Two => self.part_2.as_deref(),
+        }
+    }
+
+    pub fn stats(&self, part: Part) -> Option<&Stats> {
+        match part {
+            Part::One => self.part_1_stats.as_ref(),
+            Part::Two => self.part_2_stats.as_ref(),
+        }
+    }
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -22,16 +49,16 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate the timings of a year to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = create_data_file(year, TIMINGS_FILE_NAME)?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+    /// Rehydrate the timings of a year from a JSON file. If not present, returns empty timings.
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(data_path(year, TIMINGS_FILE_NAME))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
@@ -51,7 +78,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|t| t.day);
         Timings { data }
     }
 
@@ -60,10 +87,106 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
+    /// Returns `true` if every part of the puzzle has a timing.
+    pub fn is_day_complete(&self, puzzle: PuzzleId) -> bool {
+        self.data.iter().any(|t| {
+            t.day == puzzle.day && puzzle.parts().iter().all(|&part| t.part(part).is_some())
+        })
+    }
+}
+
+fn create_data_file(year: Year, name: &str) -> Result<fs::File, Error> {
+    let path = data_path(year, name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::File::create(path)
+}
+
+/// A set of timings that were measured together.
+#[derive(Clone, Debug)]
+pub struct TimingRun {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// The git commit the run was measured on, suffixed with `-dirty` for uncommitted changes.
+    pub commit: Option<String>,
+    pub timings: Timings,
+}
+
+impl TimingRun {
+    /// Tags timings with the current time and git commit.
+    pub fn now(timings: Timings) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        let commit = Command::new("git")
+            .args(["describe", "--always", "--dirty"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+
+        Self {
+            timestamp,
+            commit,
+            timings,
+        }
+    }
+
+    pub fn get(&self, day: Day) -> Option<&Timing> {
+        self.timings.data.iter().find(|t| t.day == day)
+    }
+}
+
+/// Represents every stored run of benchmarks, oldest first.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct TimingHistory {
+    pub runs: Vec<TimingRun>,
+}
+
+impl TimingHistory {
+    /// Dehydrate the history of a year to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let mut file = create_data_file(year, HISTORY_FILE_NAME)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the history of a year from a JSON file. If not present, returns an empty history.
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(data_path(year, HISTORY_FILE_NAME))
+            .map_err(|x| x.to_string())
+            .and_then(TimingHistory::try_from)
+            .unwrap_or_default()
+    }
+
+    pub fn append(&mut self, run: TimingRun) {
+        self.runs.push(run);
+    }
+
+    /// Every measurement of a day, oldest first.
+    pub fn for_day(&self, day: Day) -> impl Iterator<Item = (&TimingRun, &Timing)> {
+        self.runs
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .filter_map(move |run| run.get(day).map(|timing| (run, timing)))
+    }
+
+    /// The most recent measurement of a day.
+    pub fn latest(&self, day: Day) -> Option<(&TimingRun, &Timing)> {
+        self.for_day(day).last()
+    }
+
+    /// The fastest measurement of a day. Only runs that solved as many parts as the latest run are considered.
+    pub fn best(&self, day: Day) -> Option<(&TimingRun, &Timing)> {
+        let solved_parts =
+            |t: &Timing| usize::from(t.part_1.is_some()) + usize::from(t.part_2.is_some());
+        let (_, latest) = self.latest(day)?;
+
+        self.for_day(day)
+            .filter(|(_, t)| solved_parts(t) >= solved_parts(latest))
+            .min_by(|(_, a), (_, b)| a.total_nanos.total_cmp(&b.total_nanos))
     }
 }
 
@@ -107,6 +230,103 @@ impl TryFrom<String> for Timings {
 
 /* -------------------------------------------------------------------------- */
 
+impl From<&TimingHistory> for JsonValue {
+    fn from(value: &TimingHistory) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "runs".into(),
+            JsonValue::Array(value.runs.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for TimingHistory {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_runs = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("runs")
+            .ok_or("expected JSON document to have key `runs`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.runs` to be an array.")?;
+
+        Ok(TimingHistory {
+            runs: json_runs
+                .iter()
+                .map(TimingRun::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&TimingRun> for JsonValue {
+    fn from(value: &TimingRun) -> Self {
+        let mut map = match JsonValue::from(value.timings.clone()) {
+            JsonValue::Object(map) => map,
+            _ => unreachable!("timings serialize to an object"),
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for TimingRun {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected run to be a JSON object.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected run.timestamp to be a number.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected run.commit to be null or string.")?;
+
+        let timings = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected run.data to be an array.")?
+            .iter()
+            .map(Timing::try_from)
+            .collect::<Result<_, _>>()?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(TimingRun {
+            timestamp: timestamp as u64,
+            commit: commit.cloned(),
+            timings: Timings { data: timings },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
@@ -133,6 +353,16 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        for (key, stats) in [
+            ("part_1_stats", &value.part_1_stats),
+            ("part_2_stats", &value.part_2_stats),
+        ] {
+            map.insert(
+                key.into(),
+                stats.as_ref().map_or(JsonValue::Null, JsonValue::from),
+            );
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -166,10 +396,19 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: stats are optional to support timings stored before they were collected.
+        let stats = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => Stats::try_from(v).map(Some),
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            part_1_stats: stats("part_1_stats")?,
+            part_2_stats: stats("part_2_stats")?,
             total_nanos,
         })
     }
@@ -190,18 +429,24 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 4e+10,
                 },
             ],
@@ -223,6 +468,18 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000, "part_1_stats": { "samples": 10, "mean": 1000000, "median": 900000, "min": 800000, "max": 2000000, "std_dev": 10000, "p5": 800000, "p25": 850000, "p75": 950000, "p95": 1900000, "p99": 2000000, "outliers": { "low_severe": 0, "low_mild": 0, "high_mild": 1, "high_severe": 1 } }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.as_ref().unwrap();
+            assert_eq!(stats.samples, 10);
+            assert_eq!(stats.median, 900_000_f64);
+            assert_eq!(stats.outliers.total(), 2);
+            assert_eq!(timing.part_2_stats, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -272,8 +529,13 @@ mod tests {
         use crate::{
             day,
             template::timings::{Timing, Timings},
+            template::{PuzzleId, Year},
         };
 
+        fn puzzle() -> PuzzleId {
+            PuzzleId::new(Year::new(2024).unwrap(), day!(1))
+        }
+
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
@@ -281,11 +543,13 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(puzzle()), true);
         }
 
         #[test]
@@ -295,11 +559,13 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(puzzle()), false);
         }
 
         #[test]
@@ -309,11 +575,13 @@ mod tests {
                     day: day!(1),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(puzzle()), false);
         }
     }
 
@@ -333,6 +601,8 @@ mod tests {
                     day: day!(3),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -353,6 +623,8 @@ mod tests {
                     day: day!(2),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -381,4 +653,92 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
         }
     }
+
+    mod history {
+        use crate::{
+            day,
+            template::timings::{Timing, TimingHistory, TimingRun, Timings},
+        };
+
+        fn run(timestamp: u64, timings: Vec<(u8, Option<&str>, Option<&str>, f64)>) -> TimingRun {
+            TimingRun {
+                timestamp,
+                commit: Some(format!("c{timestamp}")),
+                timings: Timings {
+                    data: timings
+                        .into_iter()
+                        .map(|(day, part_1, part_2, total_nanos)| Timing {
+                            day: crate::template::Day::new(day).unwrap(),
+                            part_1: part_1.map(Into::into),
+                            part_2: part_2.map(Into::into),
+                            part_1_stats: None,
+                            part_2_stats: None,
+                            total_nanos,
+                        })
+                        .collect(),
+                },
+            }
+        }
+
+        fn get_mock_history() -> TimingHistory {
+            TimingHistory {
+                runs: vec![
+                    run(1, vec![(1, Some("1ms"), None, 1e6)]),
+                    run(
+                        2,
+                        vec![
+                            (1, Some("3ms"), Some("2ms"), 5e6),
+                            (2, Some("1ms"), None, 1e6),
+                        ],
+                    ),
+                    run(3, vec![(1, Some("2ms"), Some("2ms"), 4e6)]),
+                    run(4, vec![(1, Some("3ms"), Some("3ms"), 6e6)]),
+                ],
+            }
+        }
+
+        #[test]
+        fn queries_runs_of_day() {
+            let history = get_mock_history();
+            let timestamps: Vec<_> = history.for_day(day!(2)).map(|(r, _)| r.timestamp).collect();
+            assert_eq!(timestamps, vec![2]);
+            assert_eq!(history.for_day(day!(1)).count(), 4);
+            assert_eq!(history.for_day(day!(3)).count(), 0);
+        }
+
+        #[test]
+        fn finds_latest_run() {
+            let history = get_mock_history();
+            assert_eq!(history.latest(day!(1)).unwrap().0.timestamp, 4);
+            assert_eq!(history.latest(day!(2)).unwrap().0.timestamp, 2);
+            assert!(history.latest(day!(3)).is_none());
+        }
+
+        #[test]
+        fn finds_best_complete_run() {
+            let history = get_mock_history();
+            // run 1 is faster, but only solved part 1.
+            assert_eq!(history.best(day!(1)).unwrap().0.timestamp, 3);
+            assert!(history.best(day!(3)).is_none());
+        }
+
+        #[test]
+        fn roundtrips_json() {
+            let history = get_mock_history();
+            let json = tinyjson::JsonValue::from(&history).stringify().unwrap();
+            let parsed = TimingHistory::try_from(json).unwrap();
+            assert_eq!(parsed.runs.len(), 4);
+            assert_eq!(parsed.runs[1].commit, Some("c2".into()));
+            assert_eq!(parsed.runs[1].timings.data.len(), 2);
+            assert_eq!(parsed.runs[3].timestamp, 4);
+        }
+
+        #[test]
+        fn handles_runs_without_commit() {
+            let json =
+                r#"{ "runs": [{ "timestamp": 10, "commit": null, "data": [] }] }"#.to_string();
+            let history = TimingHistory::try_from(json).unwrap();
+            assert_eq!(history.runs[0].commit, None);
+        }
+    }
 }
@@ -0,0 +1,244 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::template::Day;
+
+/// The year of the first event.
+const FIRST_YEAR: u16 = 2015;
+
+/// A valid year of advent (i.e. 2015 or later).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2023).unwrap();
+/// assert_eq!(year.to_string(), "2023")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if there was an event that year,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < FIRST_YEAR {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The last day of the event, which has a single part. Events from 2025 on end on the 12th.
+    pub fn last_day(self) -> Day {
+        if self.0 >= 2025 {
+            crate::day!(12)
+        } else {
+            crate::day!(25)
+        }
+    }
+
+    /// The year that commands use when none is passed: `AOC_YEAR` if set, the latest event otherwise.
+    pub fn configured() -> Self {
+        env::var("AOC_YEAR")
+            .ok()
+            .and_then(|year| year.parse().ok())
+            .unwrap_or_else(Self::latest)
+    }
+
+    /// The year of the latest event that has started, events start on the 1st of december.
+    fn latest() -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let (year, month, _) = civil_date(now);
+        let year = u16::try_from(year).unwrap_or(u16::MAX);
+        Self(if month == 12 { year } else { year - 1 }.max(FIRST_YEAR))
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of 2015 or later")
+    }
+}
+
+/// Converts seconds since the unix epoch to an UTC date as `(year, month, day)`.
+pub(crate) fn civil_date(timestamp: u64) -> (u64, u64, u64) {
+    // see: https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = timestamp / 86400 + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Identifies a puzzle by its year and day.
+///
+/// Files of a puzzle are scoped by year, e.g. `data/2023/inputs/05.txt` and `src/bin/2023_05.rs`.
+/// Puzzles of the configured year may also use the unscoped layout of older versions of this template,
+/// e.g. `data/inputs/05.txt` and `src/bin/05.rs`, which is preferred if present.
+///
+/// # Display
+/// This value displays as `YYYY/DD`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// The puzzle of a solution binary. Binaries named like `2023_05` belong to that year,
+    /// any other binary belongs to the configured year.
+    pub fn from_bin_name(name: &str, day: Day) -> Self {
+        let year = name
+            .split_once('_')
+            .and_then(|(year, _)| year.parse().ok())
+            .unwrap_or_else(Year::configured);
+        Self { year, day }
+    }
+
+    /// The puzzle of a solution's source file, e.g. `src/bin/2023_05.rs`, see [`PuzzleId::from_bin_name`].
+    pub fn from_source_file(path: &str, day: Day) -> Self {
+        let name = Path::new(path)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default();
+        Self::from_bin_name(name, day)
+    }
+
+    /// The name of the solution binary, as passed to `cargo run --bin`.
+    pub fn bin_name(&self) -> String {
+        let legacy = self.day.to_string();
+        if self.year == Year::configured() && Path::new(&format!("src/bin/{legacy}.rs")).exists() {
+            legacy
+        } else {
+            format!("{}_{}", self.year, self.day)
+        }
+    }
+
+    /// The path of the solution's source file.
+    pub fn bin_path(&self) -> String {
+        format!("./src/bin/{}.rs", self.bin_name())
+    }
+
+    /// The path of a file of this puzzle in `data/`, e.g. `data/2023/inputs/05.txt`.
+    pub fn data_file(&self, folder: &str, extension: &str) -> PathBuf {
+        data_path(
+            self.year,
+            Path::new(folder).join(format!("{}.{extension}", self.day)),
+        )
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.year, self.day)
+    }
+}
+
+/// Resolves a path relative to the data folder of a year, e.g. `data/2023/timings.json`.
+///
+/// For the configured year, a file that only exists in the unscoped layout (e.g. `data/timings.json`)
+/// resolves to that file instead.
+pub fn data_path(year: Year, path: impl AsRef<Path>) -> PathBuf {
+    let data = Path::new("data");
+    let scoped = data.join(year.to_string()).join(&path);
+    let legacy = data.join(&path);
+
+    if !scoped.exists() && legacy.exists() && year == Year::configured() {
+        legacy
+    } else {
+        scoped
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{civil_date, PuzzleId, Year};
+    use crate::day;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2023".parse::<Year>().unwrap(), Year(2023));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("23".parse::<Year>().is_err());
+        assert_eq!(Year(2015).to_string(), "2015");
+    }
+
+    #[test]
+    fn reads_years_from_bin_names() {
+        assert_eq!(
+            PuzzleId::from_bin_name("2023_05", day!(5)),
+            PuzzleId::new(Year(2023), day!(5))
+        );
+        assert_eq!(
+            PuzzleId::from_bin_name("05", day!(5)),
+            PuzzleId::new(Year::configured(), day!(5))
+        );
+        assert_eq!(
+            PuzzleId::from_source_file("/aoc/src/bin/2023_05.rs", day!(5)),
+            PuzzleId::new(Year(2023), day!(5))
+        );
+        assert_eq!(
+            PuzzleId::from_source_file("src/bin/05.rs", day!(5)),
+            PuzzleId::new(Year::configured(), day!(5))
+        );
+        assert_eq!(PuzzleId::new(Year(2023), day!(5)).to_string(), "2023/05");
+    }
+
+    #[test]
+    fn converts_timestamps_to_dates() {
+        assert_eq!(civil_date(0), (1970, 1, 1));
+        assert_eq!(civil_date(1_733_011_200), (2024, 12, 1));
+    }
+}
@@ -0,0 +1,377 @@
+//! Renders grids with highlighted cells, e.g. the path of a guard, to the terminal or to image frames.
+//!
+//! Visualizations are only shown when a solution runs with `--viz` (ANSI colored text on stderr) or `--viz-frames`
+//! (PPM images in `data/viz/`). Frames are built lazily, so solutions can call [`show`] unconditionally: without the
+//! flags, and always while benchmarking with `--time`, it does nothing.
+//!
+//! ```
+//! use advent_of_code::grid::Grid;
+//! use advent_of_code::viz::{Frame, Style};
+//!
+//! let map: Grid<char> = "..#\n...".parse().unwrap();
+//! let frame = Frame::from_chars(&map).overlay([(0, 0), (1, 0)], Style::VISITED);
+//! assert_eq!(frame.to_string(), "XX#\n...");
+//! ```
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use std::{env, fmt};
+
+use crate::grid::{Grid, Position};
+use crate::template::{data_path, PuzzleId, ANSI_RESET};
+
+/// A 24-bit color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Self = Self(0x10, 0x10, 0x10);
+    pub const GRAY: Self = Self(0x80, 0x80, 0x80);
+    pub const WHITE: Self = Self(0xe0, 0xe0, 0xe0);
+    pub const RED: Self = Self(0xe0, 0x40, 0x40);
+    pub const GREEN: Self = Self(0x40, 0xc0, 0x40);
+    pub const BLUE: Self = Self(0x40, 0x80, 0xe0);
+    pub const YELLOW: Self = Self(0xe0, 0xc0, 0x30);
+}
+
+/// How an overlay changes the cells it covers. A missing glyph or color keeps the one of the cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Style {
+    pub glyph: Option<char>,
+    pub color: Option<Rgb>,
+}
+
+impl Style {
+    pub const OBSTACLE: Self = Self::new('#', Rgb::GRAY);
+    pub const VISITED: Self = Self::new('X', Rgb::YELLOW);
+    pub const PATH: Self = Self::new('O', Rgb::GREEN);
+    pub const ANTINODE: Self = Self::new('#', Rgb::RED);
+    /// Colors a cell without changing its glyph.
+    pub const HIGHLIGHT: Self = Self::color(Rgb::BLUE);
+
+    pub const fn new(glyph: char, color: Rgb) -> Self {
+        Self {
+            glyph: Some(glyph),
+            color: Some(color),
+        }
+    }
+
+    pub const fn color(color: Rgb) -> Self {
+        Self {
+            glyph: None,
+            color: Some(color),
+        }
+    }
+}
+
+/// A cell of a [`Frame`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cell {
+    pub glyph: char,
+    /// The color of the cell, uncolored cells are drawn white, or black if they are empty (`.` or a space).
+    pub color: Option<Rgb>,
+}
+
+impl Cell {
+    pub const fn new(glyph: char, color: Option<Rgb>) -> Self {
+        Self { glyph, color }
+    }
+
+    fn apply(&mut self, style: Style) {
+        self.glyph = style.glyph.unwrap_or(self.glyph);
+        self.color = style.color.or(self.color);
+    }
+
+    pub(crate) fn rgb(&self) -> Rgb {
+        self.color.unwrap_or(match self.glyph {
+            '.' | ' ' => Rgb::BLACK,
+            _ => Rgb::WHITE,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A grid of colored glyphs, displayed as plain text.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    cells: Grid<Cell>,
+}
+
+impl Frame {
+    /// A frame that draws every cell of `grid` with `draw`.
+    pub fn from_grid<T: Clone>(grid: &Grid<T>, mut draw: impl FnMut(&T) -> Cell) -> Self {
+        Self {
+            cells: grid.clone().map(|value| draw(&value)),
+        }
+    }
+
+    /// A frame of uncolored characters, e.g. of the puzzle input.
+    pub fn from_chars(grid: &Grid<char>) -> Self {
+        Self::from_grid(grid, |&glyph| Cell::new(glyph, None))
+    }
+
+    /// A frame of text, shorter lines are padded with spaces.
+    pub fn from_text(text: &str) -> Self {
+        let width = text
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let rows = text
+            .lines()
+            .map(|line| {
+                let mut row: Vec<Cell> = line.chars().map(|glyph| Cell::new(glyph, None)).collect();
+                row.resize(width, Cell::new(' ', None));
+                row
+            })
+            .collect();
+
+        Self {
+            cells: Grid::from_rows(rows).expect("rows are padded to the same width"),
+        }
+    }
+
+    /// An empty frame of spaces.
+    pub fn blank(width: usize, height: usize) -> Self {
+        Self {
+            cells: Grid::new(width, height, Cell::new(' ', None)),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    pub fn get(&self, position: Position) -> Option<&Cell> {
+        self.cells.get(position)
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut Cell> {
+        self.cells.get_mut(position)
+    }
+
+    /// The cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &Cell)> {
+        self.cells.iter()
+    }
+
+    /// The top left part of the frame that fits into `width` and `height`.
+    #[must_use]
+    pub fn cropped(&self, width: usize, height: usize) -> Self {
+        let rows = self
+            .cells
+            .rows()
+            .take(height)
+            .map(|row| row[..row.len().min(width)].to_vec())
+            .collect();
+
+        Self {
+            cells: Grid::from_rows(rows).expect("rows are cropped to the same width"),
+        }
+    }
+
+    /// Applies `style` to the cells at `positions`, positions outside of the frame are skipped.
+    #[must_use]
+    pub fn overlay(mut self, positions: impl IntoIterator<Item = Position>, style: Style) -> Self {
+        for position in positions {
+            if let Some(cell) = self.cells.get_mut(position) {
+                cell.apply(style);
+            }
+        }
+        self
+    }
+
+    /// Renders the frame with ANSI escape codes for 24-bit colors.
+    pub fn to_ansi(&self) -> String {
+        self.cells
+            .rows()
+            .map(|row| {
+                let mut line = String::new();
+                for cell in row {
+                    match cell.color {
+                        Some(Rgb(r, g, b)) => line
+                            .push_str(&format!("\x1b[38;2;{r};{g};{b}m{}{ANSI_RESET}", cell.glyph)),
+                        None => line.push(cell.glyph),
+                    }
+                }
+                line
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Renders the frame as binary PPM (P6) image, with a square of `scale` pixels per cell.
+    pub fn to_ppm(&self, scale: usize) -> Vec<u8> {
+        let scale = scale.max(1);
+        let (width, height) = (self.width() * scale, self.height() * scale);
+
+        let mut image = format!("P6\n{width} {height}\n255\n").into_bytes();
+        image.reserve(width * height * 3);
+        for row in self.cells.rows() {
+            let line: Vec<u8> = row
+                .iter()
+                .flat_map(|cell| {
+                    let Rgb(r, g, b) = cell.rgb();
+                    [r, g, b].repeat(scale)
+                })
+                .collect();
+            for _ in 0..scale {
+                image.extend_from_slice(&line);
+            }
+        }
+        image
+    }
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = self
+            .cells
+            .rows()
+            .map(|row| row.iter().map(|cell| cell.glyph).collect::<String>());
+        for (y, line) in text.enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            f.write_str(&line)?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Where visualizations go, chosen by the arguments of the solution binary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Output {
+    /// ANSI colored text on stderr, with `--viz`.
+    Terminal,
+    /// PPM images in `data/viz/<day>/`, with `--viz-frames`.
+    Frames,
+}
+
+impl Output {
+    /// The output requested by the arguments, [`None`] when benchmarking with `--time`.
+    pub fn from_args() -> Option<Self> {
+        let args: Vec<String> = env::args().collect();
+        let has = |flag: &str| args.iter().any(|arg| arg == flag);
+
+        if has("--time") {
+            None
+        } else if has("--viz-frames") {
+            Some(Self::Frames)
+        } else if has("--viz") {
+            Some(Self::Terminal)
+        } else {
+            None
+        }
+    }
+
+    /// Arguments that request this output from a solution binary.
+    pub fn to_args(self) -> Vec<String> {
+        match self {
+            Self::Terminal => vec!["--viz".into()],
+            Self::Frames => vec!["--viz-frames".into()],
+        }
+    }
+}
+
+/// The output of this process, see [`Output::from_args`].
+pub fn output() -> Option<Output> {
+    static OUTPUT: OnceLock<Option<Output>> = OnceLock::new();
+    *OUTPUT.get_or_init(Output::from_args)
+}
+
+/// Whether visualizations are shown, build expensive overlays only if so.
+pub fn enabled() -> bool {
+    output().is_some()
+}
+
+/// The pixels per cell of PPM frames.
+const FRAME_SCALE: usize = 4;
+
+/// Shows the frame built by `frame` if visualizations are enabled, with a `title` above it in the terminal.
+pub fn show(puzzle: PuzzleId, title: &str, frame: impl FnOnce() -> Frame) {
+    let Some(output) = output() else {
+        return;
+    };
+
+    let frame = frame();
+    match output {
+        Output::Terminal => eprintln!("{title}\n{}\n", frame.to_ansi()),
+        Output::Frames => match write_frame(puzzle, &frame) {
+            Ok(path) => eprintln!("{title}: {}", path.display()),
+            Err(e) => eprintln!("{title}: could not write frame: {e}"),
+        },
+    }
+}
+
+/// Writes the next numbered frame of `puzzle`, returns its path.
+fn write_frame(puzzle: PuzzleId, frame: &Frame) -> io::Result<PathBuf> {
+    static FRAMES: AtomicUsize = AtomicUsize::new(0);
+
+    let dir = frames_dir(puzzle);
+    fs::create_dir_all(&dir)?;
+
+    let number = FRAMES.fetch_add(1, Ordering::Relaxed);
+    let path = dir.join(format!("{number:05}.ppm"));
+    fs::write(&path, frame.to_ppm(FRAME_SCALE))?;
+    Ok(path)
+}
+
+/// The folder for the frames of `puzzle`, e.g. `data/2024/viz/06/`.
+pub fn frames_dir(puzzle: PuzzleId) -> PathBuf {
+    data_path(puzzle.year, Path::new("viz").join(puzzle.day.to_string()))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Cell, Frame, Rgb, Style};
+    use crate::grid::Grid;
+
+    fn frame() -> Frame {
+        let map: Grid<char> = ".#\n..".parse().unwrap();
+        Frame::from_chars(&map)
+    }
+
+    #[test]
+    fn overlays_cells() {
+        let frame = frame()
+            .overlay([(0, 1), (5, 5)], Style::PATH)
+            .overlay([(1, 0)], Style::HIGHLIGHT);
+
+        assert_eq!(frame.to_string(), ".#\nO.");
+        assert_eq!(
+            frame.get((1, 0)),
+            Some(&Cell {
+                glyph: '#',
+                color: Some(Rgb::BLUE)
+            })
+        );
+        assert_eq!(
+            frame.to_ansi().lines().next(),
+            Some(".\x1b[38;2;64;128;224m#\x1b[0m")
+        );
+    }
+
+    #[test]
+    fn renders_ppm() {
+        let image = frame().to_ppm(2);
+        let header = b"P6\n4 4\n255\n";
+
+        assert_eq!(&image[..header.len()], header);
+        assert_eq!(image.len(), header.len() + 4 * 4 * 3);
+        // the second cell of the first row is a wall, drawn white
+        assert_eq!(image[header.len() + 6..][..3], [0xe0, 0xe0, 0xe0]);
+    }
+}